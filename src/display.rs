@@ -0,0 +1,203 @@
+//! Human-readable output for `Matrix` and `Vector`. `Display` and `LowerExp`
+//! honour the formatter's precision and minimum width, e.g. `{:.3}` or `{:10.2e}`,
+//! and align every column to the widest entry. Like numpy, arrays with more than
+//! `ELISION_THRESHOLD` entries only show `EDGE_ITEMS` rows and columns at each
+//! edge. `to_latex` and `to_markdown` always print every entry.
+use crate::{Matrix, Scalar, Vector};
+use std::fmt;
+
+const ELISION_THRESHOLD: usize = 1000;
+const EDGE_ITEMS: usize = 3;
+const ELLIPSIS: &str = "...";
+
+// Indices to print along an axis of length `n`; `None` marks the elided gap.
+fn visible_indices(n: usize, elide: bool) -> Vec<Option<usize>> {
+    if elide && n > 2 * EDGE_ITEMS {
+        (0..EDGE_ITEMS)
+            .map(Some)
+            .chain(std::iter::once(None))
+            .chain((n - EDGE_ITEMS..n).map(Some))
+            .collect()
+    } else {
+        (0..n).map(Some).collect()
+    }
+}
+
+fn write_grid<F>(f: &mut fmt::Formatter<'_>, rows: usize, cols: usize, entry: F) -> fmt::Result
+where
+    F: Fn(usize, usize) -> String,
+{
+    if rows == 0 || cols == 0 {
+        return write!(f, "[]");
+    }
+
+    let elide = rows * cols > ELISION_THRESHOLD;
+    let row_indices = visible_indices(rows, elide);
+    let col_indices = visible_indices(cols, elide);
+
+    let cells: Vec<Option<Vec<String>>> = row_indices
+        .iter()
+        .map(|i| {
+            i.map(|i| {
+                col_indices
+                    .iter()
+                    .map(|j| match j {
+                        Some(j) => entry(i, *j),
+                        None => ELLIPSIS.to_string(),
+                    })
+                    .collect()
+            })
+        })
+        .collect();
+
+    let width = cells
+        .iter()
+        .flatten()
+        .flatten()
+        .map(|cell| cell.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(f.width().unwrap_or(0));
+
+    for (k, row) in cells.iter().enumerate() {
+        if k > 0 {
+            write!(f, ",\n ")?;
+        }
+        match row {
+            Some(row) => {
+                let padded: Vec<String> = row
+                    .iter()
+                    .map(|cell| format!("{:>width$}", cell, width = width))
+                    .collect();
+                write!(
+                    f,
+                    "{}[{}]",
+                    if k == 0 { "[" } else { "" },
+                    padded.join(", ")
+                )?;
+            }
+            None => write!(f, "{}", ELLIPSIS)?,
+        }
+    }
+    write!(f, "]")
+}
+
+fn write_list<F>(f: &mut fmt::Formatter<'_>, len: usize, entry: F) -> fmt::Result
+where
+    F: Fn(usize) -> String,
+{
+    let indices = visible_indices(len, len > ELISION_THRESHOLD);
+    let cells: Vec<String> = indices
+        .iter()
+        .map(|i| match i {
+            Some(i) => entry(*i),
+            None => ELLIPSIS.to_string(),
+        })
+        .collect();
+
+    let width = cells
+        .iter()
+        .map(|cell| cell.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(f.width().unwrap_or(0));
+    let padded: Vec<String> = cells
+        .iter()
+        .map(|cell| format!("{:>width$}", cell, width = width))
+        .collect();
+    write!(f, "[{}]", padded.join(", "))
+}
+
+fn plain<T: fmt::Display>(x: &T, precision: Option<usize>) -> String {
+    match precision {
+        Some(precision) => format!("{:.*}", precision, x),
+        None => format!("{}", x),
+    }
+}
+
+fn exponent<T: fmt::LowerExp>(x: &T, precision: Option<usize>) -> String {
+    match precision {
+        Some(precision) => format!("{:.*e}", precision, x),
+        None => format!("{:e}", x),
+    }
+}
+
+impl<T: Scalar + fmt::Display> fmt::Display for Matrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (rows, cols) = self.data.dim();
+        let precision = f.precision();
+        write_grid(f, rows, cols, |i, j| plain(&self.data[[i, j]], precision))
+    }
+}
+
+impl<T: Scalar + fmt::LowerExp> fmt::LowerExp for Matrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (rows, cols) = self.data.dim();
+        let precision = f.precision();
+        write_grid(f, rows, cols, |i, j| {
+            exponent(&self.data[[i, j]], precision)
+        })
+    }
+}
+
+impl<T: Scalar + fmt::Display> fmt::Display for Vector<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision();
+        write_list(f, self.data.len(), |i| plain(&self.data[i], precision))
+    }
+}
+
+impl<T: Scalar + fmt::LowerExp> fmt::LowerExp for Vector<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision();
+        write_list(f, self.data.len(), |i| exponent(&self.data[i], precision))
+    }
+}
+
+impl<T: Scalar + fmt::Display> Matrix<T> {
+    /// LaTeX `bmatrix` environment, one matrix row per line.
+    pub fn to_latex(&self) -> String {
+        let rows: Vec<String> = self
+            .data
+            .outer_iter()
+            .map(|row| {
+                let cells: Vec<String> = row.iter().map(|x| x.to_string()).collect();
+                format!("  {}", cells.join(" & "))
+            })
+            .collect();
+        format!(
+            "\\begin{{bmatrix}}\n{}\n\\end{{bmatrix}}",
+            rows.join(" \\\\\n")
+        )
+    }
+
+    /// GitHub-flavoured Markdown table. Tables need a header, so the columns
+    /// are labelled with their indices.
+    pub fn to_markdown(&self) -> String {
+        let header: Vec<String> = (0..self.data.ncols()).map(|j| j.to_string()).collect();
+        let mut lines = vec![
+            format!("| {} |", header.join(" | ")),
+            format!("|{}", "---:|".repeat(self.data.ncols())),
+        ];
+        for row in self.data.outer_iter() {
+            let cells: Vec<String> = row.iter().map(|x| x.to_string()).collect();
+            lines.push(format!("| {} |", cells.join(" | ")));
+        }
+        lines.join("\n")
+    }
+}
+
+impl<T: Scalar + fmt::Display> Vector<T> {
+    /// Vectors are exported as columns.
+    pub fn to_latex(&self) -> String {
+        self.as_column().to_latex()
+    }
+
+    pub fn to_markdown(&self) -> String {
+        self.as_column().to_markdown()
+    }
+
+    fn as_column(&self) -> Matrix<T> {
+        Matrix::from_fn(self.data.len(), 1, |i, _| self.data[i])
+    }
+}
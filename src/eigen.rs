@@ -0,0 +1,679 @@
+use crate::factorization::{all_finite, is_hermitian};
+use crate::{LinAlgError, Scalar};
+use ndarray::{Array1, Array2};
+use num_complex::Complex64;
+use num_traits::{Float, One, ToPrimitive};
+
+const MAX_SWEEPS: usize = 60;
+
+// Cyclic Jacobi eigenvalue algorithm for symmetric (Hermitian) matrices. Each
+// rotation zeroes one off-diagonal pair; the accumulated rotations are the
+// eigenvectors. Eigenvalues are real and returned in increasing order, with the
+// matching eigenvector in the same column of the second array.
+pub(crate) fn hermitian_eigen<T: Scalar>(
+    a: &Array2<T>,
+) -> Result<(Array1<T::Real>, Array2<T>), LinAlgError> {
+    let n = a.nrows();
+    if n != a.ncols() {
+        return Err(LinAlgError::NotSquare {
+            rows: n,
+            cols: a.ncols(),
+        });
+    }
+    // NaN never compares equal, so the symmetry test would misreport it.
+    if !all_finite(a) {
+        return Err(LinAlgError::NonFinite);
+    }
+    if !is_hermitian(a) {
+        return Err(LinAlgError::NotSymmetric);
+    }
+
+    let mut work = a.clone();
+    let mut vectors = Array2::<T>::eye(n);
+    let epsilon = T::Real::epsilon();
+    let two = T::Real::one() + T::Real::one();
+    let tolerance = epsilon
+        * a.iter()
+            .map(|x| x.modulus_squared())
+            .sum::<T::Real>()
+            .sqrt();
+    let mut converged = false;
+
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = work[[p, q]];
+                let magnitude = apq.modulus();
+                let (app, aqq) = (work[[p, p]].real(), work[[q, q]].real());
+                if magnitude <= tolerance || magnitude <= epsilon * (app * aqq).abs().sqrt() {
+                    continue;
+                }
+                rotated = true;
+
+                // With apq = |apq| e, the rotation [[c, s e], [-s conj(e), c]]
+                // reduces to the classical real Jacobi rotation when e = 1.
+                let phase = apq / T::from_real(magnitude);
+                let theta = (aqq - app) / (two * magnitude);
+                let t = theta.signum() / (theta.abs() + T::Real::one().hypot(theta));
+                let c = T::from_real(T::Real::one() / T::Real::one().hypot(t));
+                let s = T::from_real(t) * c;
+                let (se, sec) = (s * phase, s * phase.conj());
+
+                for k in 0..n {
+                    let (akp, akq) = (work[[k, p]], work[[k, q]]);
+                    work[[k, p]] = c * akp - sec * akq;
+                    work[[k, q]] = se * akp + c * akq;
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (work[[p, k]], work[[q, k]]);
+                    work[[p, k]] = c * apk - se * aqk;
+                    work[[q, k]] = sec * apk + c * aqk;
+                }
+                for k in 0..n {
+                    let (vkp, vkq) = (vectors[[k, p]], vectors[[k, q]]);
+                    vectors[[k, p]] = c * vkp - sec * vkq;
+                    vectors[[k, q]] = se * vkp + c * vkq;
+                }
+            }
+        }
+
+        if !rotated {
+            converged = true;
+            break;
+        }
+    }
+
+    if !converged {
+        let off_diagonal: T::Real = work
+            .indexed_iter()
+            .filter(|((i, j), _)| i != j)
+            .map(|(_, x)| x.modulus_squared())
+            .sum();
+        return Err(LinAlgError::NoConvergence {
+            iterations: MAX_SWEEPS,
+            residual: off_diagonal.sqrt().to_f64().unwrap_or(f64::NAN),
+        });
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| {
+        work[[i, i]]
+            .real()
+            .partial_cmp(&work[[j, j]].real())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let values: Array1<T::Real> = order.iter().map(|&i| work[[i, i]].real()).collect();
+    let mut sorted_vectors = Array2::<T>::zeros((n, n));
+    for (k, &i) in order.iter().enumerate() {
+        sorted_vectors.column_mut(k).assign(&vectors.column(i));
+    }
+
+    Ok((values, sorted_vectors))
+}
+
+// Iterations allowed per eigenvalue before the QR algorithm gives up.
+const MAX_QR_ITERATIONS: usize = 100;
+
+/// Result of `Matrix::eigen`. Complex conjugate pairs appear next to each
+/// other, the one with positive imaginary part first. Each eigenvector column
+/// has unit 2-norm.
+pub struct EigenDecomposition {
+    pub eigenvalues: Array1<Complex64>,
+    pub eigenvectors: Option<Array2<Complex64>>,
+}
+
+// Eigenvalues and optionally right eigenvectors of a general real matrix.
+// The matrix is reduced to upper Hessenberg form by orthogonal similarity
+// transformations and then to real Schur form by Francis double-shift QR
+// steps, following the EISPACK routines orthes and hqr2.
+pub(crate) fn eigen(
+    a: &Array2<f64>,
+    compute_vectors: bool,
+) -> Result<EigenDecomposition, LinAlgError> {
+    let n = a.nrows();
+    if n != a.ncols() {
+        return Err(LinAlgError::NotSquare {
+            rows: n,
+            cols: a.ncols(),
+        });
+    }
+
+    let mut h: Vec<Vec<f64>> = a.outer_iter().map(|row| row.to_vec()).collect();
+    let mut v = vec![vec![0.0; n]; n];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+
+    if n > 0 {
+        hessenberg(&mut h, &mut v);
+    }
+    let (d, e) = schur(&mut h, &mut v, compute_vectors)?;
+
+    let eigenvalues: Array1<Complex64> = (0..n).map(|i| Complex64::new(d[i], e[i])).collect();
+
+    let eigenvectors = if compute_vectors {
+        let mut vectors = Array2::<Complex64>::zeros((n, n));
+        let mut j = 0;
+        while j < n {
+            if e[j] == 0.0 {
+                for i in 0..n {
+                    vectors[[i, j]] = Complex64::new(v[i][j], 0.0);
+                }
+                j += 1;
+            } else {
+                // Columns j and j + 1 hold the real and imaginary parts of
+                // the eigenvector for d[j] + i e[j]; its conjugate belongs to
+                // the conjugate eigenvalue.
+                for i in 0..n {
+                    vectors[[i, j]] = Complex64::new(v[i][j], v[i][j + 1]);
+                    vectors[[i, j + 1]] = Complex64::new(v[i][j], -v[i][j + 1]);
+                }
+                j += 2;
+            }
+        }
+
+        for mut column in vectors.columns_mut() {
+            let norm = column.iter().map(|z| z.norm_sqr()).sum::<f64>().sqrt();
+            if norm > 0.0 {
+                column.mapv_inplace(|z| z / norm);
+            }
+        }
+
+        Some(vectors)
+    } else {
+        None
+    };
+
+    Ok(EigenDecomposition {
+        eigenvalues,
+        eigenvectors,
+    })
+}
+
+// Householder reduction to upper Hessenberg form, accumulating the
+// transformations in `v`.
+#[allow(clippy::needless_range_loop)]
+fn hessenberg(h: &mut [Vec<f64>], v: &mut [Vec<f64>]) {
+    let n = h.len();
+    let high = n - 1;
+    let mut ort = vec![0.0; n];
+
+    for m in 1..high {
+        let scale: f64 = (m..=high).map(|i| h[i][m - 1].abs()).sum();
+        if scale == 0.0 {
+            continue;
+        }
+
+        let mut hh = 0.0;
+        for i in (m..=high).rev() {
+            ort[i] = h[i][m - 1] / scale;
+            hh += ort[i] * ort[i];
+        }
+        let mut g = hh.sqrt();
+        if ort[m] > 0.0 {
+            g = -g;
+        }
+        hh -= ort[m] * g;
+        ort[m] -= g;
+
+        for j in m..n {
+            let f = (m..=high).rev().map(|i| ort[i] * h[i][j]).sum::<f64>() / hh;
+            for i in m..=high {
+                h[i][j] -= f * ort[i];
+            }
+        }
+
+        for row in h.iter_mut().take(high + 1) {
+            let f = (m..=high).rev().map(|j| ort[j] * row[j]).sum::<f64>() / hh;
+            for j in m..=high {
+                row[j] -= f * ort[j];
+            }
+        }
+
+        ort[m] *= scale;
+        h[m][m - 1] = scale * g;
+    }
+
+    for m in (1..high).rev() {
+        if h[m][m - 1] == 0.0 {
+            continue;
+        }
+        for i in m + 1..=high {
+            ort[i] = h[i][m - 1];
+        }
+        for j in m..=high {
+            let g: f64 = (m..=high).map(|i| ort[i] * v[i][j]).sum();
+            // Double division avoids possible underflow
+            let g = (g / ort[m]) / h[m][m - 1];
+            for i in m..=high {
+                v[i][j] += g * ort[i];
+            }
+        }
+    }
+}
+
+// Reduces the Hessenberg matrix `h` to real Schur form and returns the real
+// and imaginary parts of the eigenvalues. When `compute_vectors` is set, `v`
+// is overwritten with the (unnormalized) eigenvectors, complex pairs stored as
+// consecutive real and imaginary columns.
+#[allow(clippy::many_single_char_names, clippy::needless_range_loop)]
+fn schur(
+    h: &mut [Vec<f64>],
+    v: &mut [Vec<f64>],
+    compute_vectors: bool,
+) -> Result<(Vec<f64>, Vec<f64>), LinAlgError> {
+    let nn = h.len();
+    let mut d = vec![0.0; nn];
+    let mut e = vec![0.0; nn];
+    if nn == 0 {
+        return Ok((d, e));
+    }
+
+    let low = 0isize;
+    let eps = f64::EPSILON;
+    let mut exshift = 0.0;
+    let (mut p, mut q, mut r) = (0.0, 0.0, 0.0);
+    let (mut s, mut z, mut w, mut x, mut y);
+
+    let mut norm = 0.0;
+    for (i, row) in h.iter().enumerate() {
+        for value in row.iter().skip(i.saturating_sub(1)) {
+            norm += value.abs();
+        }
+    }
+
+    let mut n = nn as isize - 1;
+    let mut iter = 0;
+    while n >= low {
+        let nu = n as usize;
+
+        // Look for a single small sub-diagonal element
+        let mut l = n;
+        while l > low {
+            let lu = l as usize;
+            s = h[lu - 1][lu - 1].abs() + h[lu][lu].abs();
+            if s == 0.0 {
+                s = norm;
+            }
+            if h[lu][lu - 1].abs() <= eps * s {
+                break;
+            }
+            l -= 1;
+        }
+
+        if l == n {
+            // One root found
+            h[nu][nu] += exshift;
+            d[nu] = h[nu][nu];
+            e[nu] = 0.0;
+            n -= 1;
+            iter = 0;
+        } else if l == n - 1 {
+            // Two roots found
+            w = h[nu][nu - 1] * h[nu - 1][nu];
+            p = (h[nu - 1][nu - 1] - h[nu][nu]) / 2.0;
+            q = p * p + w;
+            z = q.abs().sqrt();
+            h[nu][nu] += exshift;
+            h[nu - 1][nu - 1] += exshift;
+            x = h[nu][nu];
+
+            if q >= 0.0 {
+                // Real pair
+                z = if p >= 0.0 { p + z } else { p - z };
+                d[nu - 1] = x + z;
+                d[nu] = d[nu - 1];
+                if z != 0.0 {
+                    d[nu] = x - w / z;
+                }
+                e[nu - 1] = 0.0;
+                e[nu] = 0.0;
+                x = h[nu][nu - 1];
+                s = x.abs() + z.abs();
+                p = x / s;
+                q = z / s;
+                r = (p * p + q * q).sqrt();
+                p /= r;
+                q /= r;
+
+                // Row modification
+                for j in nu - 1..nn {
+                    z = h[nu - 1][j];
+                    h[nu - 1][j] = q * z + p * h[nu][j];
+                    h[nu][j] = q * h[nu][j] - p * z;
+                }
+
+                // Column modification
+                for row in h.iter_mut().take(nu + 1) {
+                    z = row[nu - 1];
+                    row[nu - 1] = q * z + p * row[nu];
+                    row[nu] = q * row[nu] - p * z;
+                }
+
+                // Accumulate transformations
+                if compute_vectors {
+                    for row in v.iter_mut() {
+                        z = row[nu - 1];
+                        row[nu - 1] = q * z + p * row[nu];
+                        row[nu] = q * row[nu] - p * z;
+                    }
+                }
+            } else {
+                // Complex pair
+                d[nu - 1] = x + p;
+                d[nu] = x + p;
+                e[nu - 1] = z;
+                e[nu] = -z;
+            }
+            n -= 2;
+            iter = 0;
+        } else {
+            // No convergence yet
+            if iter >= MAX_QR_ITERATIONS {
+                return Err(LinAlgError::NoConvergence {
+                    iterations: iter,
+                    residual: h[nu][nu - 1].abs(),
+                });
+            }
+
+            // Form shift
+            x = h[nu][nu];
+            y = 0.0;
+            w = 0.0;
+            if l < n {
+                y = h[nu - 1][nu - 1];
+                w = h[nu][nu - 1] * h[nu - 1][nu];
+            }
+
+            // Wilkinson's original ad hoc shift
+            if iter == 10 {
+                exshift += x;
+                for (i, row) in h.iter_mut().enumerate().take(nu + 1) {
+                    row[i] -= x;
+                }
+                s = h[nu][nu - 1].abs() + h[nu - 1][nu - 2].abs();
+                x = 0.75 * s;
+                y = x;
+                w = -0.4375 * s * s;
+            }
+
+            // MATLAB's ad hoc shift
+            if iter == 30 {
+                s = (y - x) / 2.0;
+                s = s * s + w;
+                if s > 0.0 {
+                    s = s.sqrt();
+                    if y < x {
+                        s = -s;
+                    }
+                    s = x - w / ((y - x) / 2.0 + s);
+                    for (i, row) in h.iter_mut().enumerate().take(nu + 1) {
+                        row[i] -= s;
+                    }
+                    exshift += s;
+                    x = 0.964;
+                    y = x;
+                    w = x;
+                }
+            }
+
+            iter += 1;
+
+            // Look for two consecutive small sub-diagonal elements
+            let mut m = n - 2;
+            while m >= l {
+                let mu = m as usize;
+                z = h[mu][mu];
+                r = x - z;
+                s = y - z;
+                p = (r * s - w) / h[mu + 1][mu] + h[mu][mu + 1];
+                q = h[mu + 1][mu + 1] - z - r - s;
+                r = h[mu + 2][mu + 1];
+                s = p.abs() + q.abs() + r.abs();
+                p /= s;
+                q /= s;
+                r /= s;
+                if m == l {
+                    break;
+                }
+                if h[mu][mu - 1].abs() * (q.abs() + r.abs())
+                    < eps
+                        * (p.abs() * (h[mu - 1][mu - 1].abs() + z.abs() + h[mu + 1][mu + 1].abs()))
+                {
+                    break;
+                }
+                m -= 1;
+            }
+            let mu = m as usize;
+
+            for i in mu + 2..=nu {
+                h[i][i - 2] = 0.0;
+                if i > mu + 2 {
+                    h[i][i - 3] = 0.0;
+                }
+            }
+
+            // Double QR step involving rows l..=n and columns m..=n
+            for k in mu..nu {
+                let notlast = k != nu - 1;
+                if k != mu {
+                    p = h[k][k - 1];
+                    q = h[k + 1][k - 1];
+                    r = if notlast { h[k + 2][k - 1] } else { 0.0 };
+                    x = p.abs() + q.abs() + r.abs();
+                    if x == 0.0 {
+                        continue;
+                    }
+                    p /= x;
+                    q /= x;
+                    r /= x;
+                }
+
+                s = (p * p + q * q + r * r).sqrt();
+                if p < 0.0 {
+                    s = -s;
+                }
+                if s == 0.0 {
+                    continue;
+                }
+
+                if k != mu {
+                    h[k][k - 1] = -s * x;
+                } else if l != m {
+                    h[k][k - 1] = -h[k][k - 1];
+                }
+                p += s;
+                x = p / s;
+                y = q / s;
+                z = r / s;
+                q /= p;
+                r /= p;
+
+                // Row modification
+                for j in k..nn {
+                    p = h[k][j] + q * h[k + 1][j];
+                    if notlast {
+                        p += r * h[k + 2][j];
+                        h[k + 2][j] -= p * z;
+                    }
+                    h[k][j] -= p * x;
+                    h[k + 1][j] -= p * y;
+                }
+
+                // Column modification
+                for row in h.iter_mut().take(nu.min(k + 3) + 1) {
+                    p = x * row[k] + y * row[k + 1];
+                    if notlast {
+                        p += z * row[k + 2];
+                        row[k + 2] -= p * r;
+                    }
+                    row[k] -= p;
+                    row[k + 1] -= p * q;
+                }
+
+                // Accumulate transformations
+                if compute_vectors {
+                    for row in v.iter_mut() {
+                        p = x * row[k] + y * row[k + 1];
+                        if notlast {
+                            p += z * row[k + 2];
+                            row[k + 2] -= p * r;
+                        }
+                        row[k] -= p;
+                        row[k + 1] -= p * q;
+                    }
+                }
+            }
+        }
+    }
+
+    if compute_vectors && norm != 0.0 {
+        back_substitute(h, v, &d, &e, norm);
+    }
+
+    Ok((d, e))
+}
+
+// Computes the eigenvectors of the quasi-triangular Schur form in `h` and maps
+// them back through the accumulated transformations in `v`.
+#[allow(clippy::many_single_char_names)]
+fn back_substitute(h: &mut [Vec<f64>], v: &mut [Vec<f64>], d: &[f64], e: &[f64], norm: f64) {
+    let nn = h.len();
+    let eps = f64::EPSILON;
+    let (mut r, mut s, mut z) = (0.0, 0.0, 0.0);
+    let (mut t, mut w, mut x, mut y);
+
+    for n in (0..nn).rev() {
+        let p = d[n];
+        let q = e[n];
+
+        if q == 0.0 {
+            // Real vector
+            let mut l = n;
+            h[n][n] = 1.0;
+            for i in (0..n).rev() {
+                w = h[i][i] - p;
+                r = (l..=n).map(|j| h[i][j] * h[j][n]).sum();
+                if e[i] < 0.0 {
+                    z = w;
+                    s = r;
+                    continue;
+                }
+
+                l = i;
+                if e[i] == 0.0 {
+                    h[i][n] = if w != 0.0 { -r / w } else { -r / (eps * norm) };
+                } else {
+                    // Solve real equations
+                    x = h[i][i + 1];
+                    y = h[i + 1][i];
+                    let denominator = (d[i] - p) * (d[i] - p) + e[i] * e[i];
+                    t = (x * s - z * r) / denominator;
+                    h[i][n] = t;
+                    h[i + 1][n] = if x.abs() > z.abs() {
+                        (-r - w * t) / x
+                    } else {
+                        (-s - y * t) / z
+                    };
+                }
+
+                // Overflow control
+                t = h[i][n].abs();
+                if (eps * t) * t > 1.0 {
+                    for row in h.iter_mut().take(n + 1).skip(i) {
+                        row[n] /= t;
+                    }
+                }
+            }
+        } else if q < 0.0 {
+            // Complex vector, stored in columns n - 1 (real) and n (imaginary)
+            let mut l = n - 1;
+
+            // Last vector component imaginary so matrix is triangular
+            if h[n][n - 1].abs() > h[n - 1][n].abs() {
+                h[n - 1][n - 1] = q / h[n][n - 1];
+                h[n - 1][n] = -(h[n][n] - p) / h[n][n - 1];
+            } else {
+                let (cr, ci) = cdiv(0.0, -h[n - 1][n], h[n - 1][n - 1] - p, q);
+                h[n - 1][n - 1] = cr;
+                h[n - 1][n] = ci;
+            }
+            h[n][n - 1] = 0.0;
+            h[n][n] = 1.0;
+
+            for i in (0..n - 1).rev() {
+                let ra: f64 = (l..=n).map(|j| h[i][j] * h[j][n - 1]).sum();
+                let sa: f64 = (l..=n).map(|j| h[i][j] * h[j][n]).sum();
+                w = h[i][i] - p;
+
+                if e[i] < 0.0 {
+                    z = w;
+                    r = ra;
+                    s = sa;
+                    continue;
+                }
+
+                l = i;
+                if e[i] == 0.0 {
+                    let (cr, ci) = cdiv(-ra, -sa, w, q);
+                    h[i][n - 1] = cr;
+                    h[i][n] = ci;
+                } else {
+                    // Solve complex equations
+                    x = h[i][i + 1];
+                    y = h[i + 1][i];
+                    let mut vr = (d[i] - p) * (d[i] - p) + e[i] * e[i] - q * q;
+                    let vi = (d[i] - p) * 2.0 * q;
+                    if vr == 0.0 && vi == 0.0 {
+                        vr = eps * norm * (w.abs() + q.abs() + x.abs() + y.abs() + z.abs());
+                    }
+                    let (cr, ci) = cdiv(x * r - z * ra + q * sa, x * s - z * sa - q * ra, vr, vi);
+                    h[i][n - 1] = cr;
+                    h[i][n] = ci;
+                    if x.abs() > z.abs() + q.abs() {
+                        h[i + 1][n - 1] = (-ra - w * h[i][n - 1] + q * h[i][n]) / x;
+                        h[i + 1][n] = (-sa - w * h[i][n] - q * h[i][n - 1]) / x;
+                    } else {
+                        let (cr, ci) = cdiv(-r - y * h[i][n - 1], -s - y * h[i][n], z, q);
+                        h[i + 1][n - 1] = cr;
+                        h[i + 1][n] = ci;
+                    }
+                }
+
+                // Overflow control
+                t = h[i][n - 1].abs().max(h[i][n].abs());
+                if (eps * t) * t > 1.0 {
+                    for row in h.iter_mut().take(n + 1).skip(i) {
+                        row[n - 1] /= t;
+                        row[n] /= t;
+                    }
+                }
+            }
+        }
+    }
+
+    // Back transformation to get eigenvectors of the original matrix
+    for j in (0..nn).rev() {
+        for row in v.iter_mut() {
+            z = (0..=j).map(|k| row[k] * h[k][j]).sum();
+            row[j] = z;
+        }
+    }
+}
+
+// Complex division (xr + i xi) / (yr + i yi) using Smith's method to avoid
+// overflow.
+fn cdiv(xr: f64, xi: f64, yr: f64, yi: f64) -> (f64, f64) {
+    if yr.abs() > yi.abs() {
+        let r = yi / yr;
+        let d = yr + r * yi;
+        ((xr + r * xi) / d, (xi - r * xr) / d)
+    } else {
+        let r = yr / yi;
+        let d = yi + r * yr;
+        ((r * xr + xi) / d, (r * xi - xr) / d)
+    }
+}
@@ -0,0 +1,75 @@
+use std::error::Error;
+use std::fmt;
+
+/// Error returned by every fallible operation in the crate.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinAlgError {
+    /// The sizes of two operands do not agree along the dimension that must
+    /// match, e.g. the inner dimension of a product or the length of a
+    /// right-hand side.
+    DimensionMismatch { expected: usize, found: usize },
+    NotSquare { rows: usize, cols: usize },
+    /// Not symmetric, or for complex matrices not Hermitian.
+    NotSymmetric,
+    /// A zero pivot was met in the given column during factorization.
+    Singular { pivot: usize },
+    /// The Cholesky pivot in the given column was zero, negative or NaN.
+    NotPositiveDefinite { pivot: usize },
+    /// A QR solve needs at least as many rows as columns.
+    Underdetermined { rows: usize, cols: usize },
+    ZeroVector,
+    /// The input contains NaN or infinite entries.
+    NonFinite,
+    /// The reciprocal condition number estimate fell below the caller's
+    /// threshold, so a solution would not be trustworthy.
+    IllConditioned { rcond: f64 },
+    /// An iterative method stopped after `iterations` steps with `residual`
+    /// still above its tolerance.
+    NoConvergence { iterations: usize, residual: f64 },
+}
+
+impl fmt::Display for LinAlgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinAlgError::DimensionMismatch { expected, found } => write!(
+                f,
+                "Dimension mismatch: expected {}, found {}",
+                expected, found
+            ),
+            LinAlgError::NotSquare { rows, cols } => {
+                write!(f, "Matrix must be square, but is {} x {}", rows, cols)
+            }
+            LinAlgError::NotSymmetric => write!(f, "Matrix must be symmetric"),
+            LinAlgError::Singular { pivot } => {
+                write!(f, "Matrix is singular: zero pivot in column {}", pivot)
+            }
+            LinAlgError::NotPositiveDefinite { pivot } => write!(
+                f,
+                "Matrix is not positive definite: pivot {} is not positive",
+                pivot
+            ),
+            LinAlgError::Underdetermined { rows, cols } => write!(
+                f,
+                "System is underdetermined: {} rows but {} columns",
+                rows, cols
+            ),
+            LinAlgError::ZeroVector => write!(f, "Vector must be nonzero"),
+            LinAlgError::NonFinite => write!(f, "Matrix contains NaN or infinite entries"),
+            LinAlgError::IllConditioned { rcond } => write!(
+                f,
+                "Matrix is ill-conditioned: reciprocal condition number {:e}",
+                rcond
+            ),
+            LinAlgError::NoConvergence {
+                iterations,
+                residual,
+            } => write!(
+                f,
+                "Did not converge after {} iterations (residual {:e})",
+                iterations, residual
+            ),
+        }
+    }
+}
+
+impl Error for LinAlgError {}
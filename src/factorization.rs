@@ -0,0 +1,1077 @@
+use crate::scalar::nan_max;
+use crate::{parallel, LinAlgError, Matrix, Scalar, Vector};
+use ndarray::{Array1, Array2, ArrayView1, Axis};
+use num_traits::{Float, NumCast, One, Zero};
+use rayon::prelude::*;
+
+/// LU factorization with partial pivoting, PA = LU. L (unit diagonal implied)
+/// and U are packed into a single array.
+#[derive(Clone)]
+pub struct LuFactor<T: Scalar = f64> {
+    lu: Array2<T>,
+    permutation: Vec<usize>,
+    swaps: usize,
+    norm: T::Real,
+}
+
+impl<T: Scalar> LuFactor<T> {
+    pub(crate) fn new(a: &Array2<T>) -> Result<LuFactor<T>, LinAlgError> {
+        let n = a.nrows();
+        if n != a.ncols() {
+            return Err(LinAlgError::NotSquare {
+                rows: n,
+                cols: a.ncols(),
+            });
+        }
+
+        let mut lu = a.clone();
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut swaps = 0;
+
+        for k in 0..n {
+            let pivot_row = (k + 1..n).fold(k, |best, i| {
+                if lu[[i, k]].modulus() > lu[[best, k]].modulus() {
+                    i
+                } else {
+                    best
+                }
+            });
+
+            if lu[[pivot_row, k]] == T::zero() {
+                return Err(LinAlgError::Singular { pivot: k });
+            }
+
+            if pivot_row != k {
+                for j in 0..n {
+                    lu.swap([k, j], [pivot_row, j]);
+                }
+                permutation.swap(k, pivot_row);
+                swaps += 1;
+            }
+
+            for i in k + 1..n {
+                let factor = lu[[i, k]] / lu[[k, k]];
+                lu[[i, k]] = factor;
+                for j in k + 1..n {
+                    let update = factor * lu[[k, j]];
+                    lu[[i, j]] -= update;
+                }
+            }
+        }
+
+        Ok(LuFactor {
+            lu,
+            permutation,
+            swaps,
+            norm: one_norm(a),
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.lu.nrows()
+    }
+
+    /// Row `i` of PA is row `permutation()[i]` of A.
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    pub fn swaps(&self) -> usize {
+        self.swaps
+    }
+
+    pub fn p(&self) -> Matrix<T> {
+        let n = self.size();
+        let mut p = Array2::<T>::zeros((n, n));
+        for (i, &row) in self.permutation.iter().enumerate() {
+            p[[i, row]] = T::one();
+        }
+        Matrix { data: p }
+    }
+
+    pub fn l(&self) -> Matrix<T> {
+        let n = self.size();
+        let data = Array2::from_shape_fn((n, n), |(i, j)| match i.cmp(&j) {
+            std::cmp::Ordering::Greater => self.lu[[i, j]],
+            std::cmp::Ordering::Equal => T::one(),
+            std::cmp::Ordering::Less => T::zero(),
+        });
+        Matrix { data }
+    }
+
+    pub fn u(&self) -> Matrix<T> {
+        let n = self.size();
+        let data = Array2::from_shape_fn(
+            (n, n),
+            |(i, j)| {
+                if i <= j {
+                    self.lu[[i, j]]
+                } else {
+                    T::zero()
+                }
+            },
+        );
+        Matrix { data }
+    }
+
+    pub fn solve(&self, b: &Vector<T>) -> Result<Vector<T>, LinAlgError> {
+        if b.data.len() != self.size() {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: self.size(),
+                found: b.data.len(),
+            });
+        }
+
+        Ok(Vector {
+            data: self.substitute(b.data.view()),
+        })
+    }
+
+    pub fn solve_matrix(&self, b: &Matrix<T>) -> Result<Matrix<T>, LinAlgError> {
+        if b.data.nrows() != self.size() {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: self.size(),
+                found: b.data.nrows(),
+            });
+        }
+
+        Ok(Matrix {
+            data: solve_columns(&b.data, self.size(), |column| self.substitute(column)),
+        })
+    }
+
+    /// Solves A^T x = b with the same factorization.
+    pub fn solve_transpose(&self, b: &Vector<T>) -> Result<Vector<T>, LinAlgError> {
+        if b.data.len() != self.size() {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: self.size(),
+                found: b.data.len(),
+            });
+        }
+
+        Ok(Vector {
+            data: self.substitute_transpose(b.data.view()),
+        })
+    }
+
+    pub fn determinant(&self) -> T {
+        let product = self.lu.diag().iter().copied().product::<T>();
+        if self.swaps % 2 == 1 {
+            T::zero() - product
+        } else {
+            product
+        }
+    }
+
+    pub fn inverse(&self) -> Matrix<T> {
+        Matrix {
+            data: solve_columns(&Array2::eye(self.size()), self.size(), |column| {
+                self.substitute(column)
+            }),
+        }
+    }
+
+    fn substitute(&self, b: ArrayView1<T>) -> Array1<T> {
+        let n = self.size();
+        let mut x: Array1<T> = self.permutation.iter().map(|&row| b[row]).collect();
+
+        for i in 0..n {
+            for j in 0..i {
+                let update = self.lu[[i, j]] * x[j];
+                x[i] -= update;
+            }
+        }
+
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                let update = self.lu[[i, j]] * x[j];
+                x[i] -= update;
+            }
+            x[i] /= self.lu[[i, i]];
+        }
+
+        x
+    }
+
+    // A^T = U^T L^T P, so solve U^T then L^T and undo the permutation.
+    fn substitute_transpose(&self, b: ArrayView1<T>) -> Array1<T> {
+        let n = self.size();
+        let mut y = b.to_owned();
+
+        for i in 0..n {
+            for j in 0..i {
+                let update = self.lu[[j, i]] * y[j];
+                y[i] -= update;
+            }
+            y[i] /= self.lu[[i, i]];
+        }
+
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                let update = self.lu[[j, i]] * y[j];
+                y[i] -= update;
+            }
+        }
+
+        let mut x = Array1::<T>::zeros(n);
+        for (i, &row) in self.permutation.iter().enumerate() {
+            x[row] = y[i];
+        }
+
+        x
+    }
+}
+
+impl LuFactor<f64> {
+    /// Estimate of 1 / (||A||_1 ||A^-1||_1). Close to 1 for well-conditioned
+    /// matrices and close to machine epsilon for nearly singular ones.
+    pub fn rcond(&self) -> f64 {
+        reciprocal_condition(
+            self.norm,
+            self.size(),
+            |x| self.substitute(x),
+            |x| self.substitute_transpose(x),
+        )
+    }
+}
+
+/// Cholesky factorization A = LL^T of a symmetric positive-definite matrix.
+#[derive(Clone)]
+pub struct CholeskyFactor {
+    l: Array2<f64>,
+    norm: f64,
+}
+
+impl CholeskyFactor {
+    pub(crate) fn new(a: &Array2<f64>) -> Result<CholeskyFactor, LinAlgError> {
+        let n = a.nrows();
+        if n != a.ncols() {
+            return Err(LinAlgError::NotSquare {
+                rows: n,
+                cols: a.ncols(),
+            });
+        }
+        if !is_hermitian(a) {
+            return Err(LinAlgError::NotSymmetric);
+        }
+
+        let mut l = Array2::<f64>::zeros((n, n));
+        for j in 0..n {
+            let pivot = a[[j, j]] - (0..j).map(|k| l[[j, k]] * l[[j, k]]).sum::<f64>();
+            if pivot.is_nan() || pivot <= 0.0 {
+                return Err(LinAlgError::NotPositiveDefinite { pivot: j });
+            }
+            l[[j, j]] = pivot.sqrt();
+
+            for i in j + 1..n {
+                let sum: f64 = (0..j).map(|k| l[[i, k]] * l[[j, k]]).sum();
+                l[[i, j]] = (a[[i, j]] - sum) / l[[j, j]];
+            }
+        }
+
+        Ok(CholeskyFactor {
+            l,
+            norm: one_norm(a),
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.l.nrows()
+    }
+
+    pub fn l(&self) -> Matrix {
+        Matrix {
+            data: self.l.clone(),
+        }
+    }
+
+    pub fn solve(&self, b: &Vector) -> Result<Vector, LinAlgError> {
+        if b.data.len() != self.size() {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: self.size(),
+                found: b.data.len(),
+            });
+        }
+
+        Ok(Vector {
+            data: self.substitute(b.data.view()),
+        })
+    }
+
+    pub fn solve_matrix(&self, b: &Matrix) -> Result<Matrix, LinAlgError> {
+        if b.data.nrows() != self.size() {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: self.size(),
+                found: b.data.nrows(),
+            });
+        }
+
+        Ok(Matrix {
+            data: solve_columns(&b.data, self.size(), |column| self.substitute(column)),
+        })
+    }
+
+    /// A is symmetric, so this is the same as `solve`.
+    pub fn solve_transpose(&self, b: &Vector) -> Result<Vector, LinAlgError> {
+        self.solve(b)
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let diag_product: f64 = self.l.diag().iter().product();
+        diag_product * diag_product
+    }
+
+    pub fn inverse(&self) -> Matrix {
+        Matrix {
+            data: solve_columns(&Array2::eye(self.size()), self.size(), |column| {
+                self.substitute(column)
+            }),
+        }
+    }
+
+    pub fn rcond(&self) -> f64 {
+        reciprocal_condition(
+            self.norm,
+            self.size(),
+            |x| self.substitute(x),
+            |x| self.substitute(x),
+        )
+    }
+
+    fn substitute(&self, b: ArrayView1<f64>) -> Array1<f64> {
+        let n = self.size();
+        let mut x = b.to_owned();
+
+        for i in 0..n {
+            for j in 0..i {
+                x[i] -= self.l[[i, j]] * x[j];
+            }
+            x[i] /= self.l[[i, i]];
+        }
+
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                x[i] -= self.l[[j, i]] * x[j];
+            }
+            x[i] /= self.l[[i, i]];
+        }
+
+        x
+    }
+}
+
+/// Symmetric indefinite factorization PAP^T = LDL^T with Bunch-Kaufman
+/// pivoting. L is unit lower triangular and D is block diagonal with 1x1 and
+/// 2x2 blocks.
+#[derive(Clone)]
+pub struct LdltFactor {
+    l: Array2<f64>,
+    d: Array2<f64>,
+    blocks: Vec<usize>,
+    permutation: Vec<usize>,
+}
+
+impl LdltFactor {
+    pub(crate) fn new(a: &Array2<f64>) -> Result<LdltFactor, LinAlgError> {
+        let n = a.nrows();
+        if n != a.ncols() {
+            return Err(LinAlgError::NotSquare {
+                rows: n,
+                cols: a.ncols(),
+            });
+        }
+        if !is_hermitian(a) {
+            return Err(LinAlgError::NotSymmetric);
+        }
+
+        // Growth-minimizing threshold from Bunch and Kaufman (1977).
+        let alpha = (1.0 + 17f64.sqrt()) / 8.0;
+
+        let mut work = a.clone();
+        let mut l = Array2::<f64>::eye(n);
+        let mut d = Array2::<f64>::zeros((n, n));
+        let mut blocks = Vec::new();
+        let mut permutation: Vec<usize> = (0..n).collect();
+
+        let mut k = 0;
+        while k < n {
+            let diagonal = work[[k, k]].abs();
+            let (r, column_max) = (k + 1..n).fold((k, 0.0), |(best, max), i| {
+                if work[[i, k]].abs() > max {
+                    (i, work[[i, k]].abs())
+                } else {
+                    (best, max)
+                }
+            });
+
+            let (pivot, size) = if diagonal.max(column_max) == 0.0 || diagonal >= alpha * column_max
+            {
+                (k, 1)
+            } else {
+                let row_max = (k..n)
+                    .filter(|&j| j != r)
+                    .map(|j| work[[r, j]].abs())
+                    .fold(0.0, f64::max);
+                if diagonal * row_max >= alpha * column_max * column_max {
+                    (k, 1)
+                } else if work[[r, r]].abs() >= alpha * row_max {
+                    (r, 1)
+                } else {
+                    (r, 2)
+                }
+            };
+
+            let target = k + size - 1;
+            if pivot != target {
+                for j in 0..n {
+                    work.swap([target, j], [pivot, j]);
+                }
+                for i in 0..n {
+                    work.swap([i, target], [i, pivot]);
+                }
+                for j in 0..k {
+                    l.swap([target, j], [pivot, j]);
+                }
+                permutation.swap(target, pivot);
+            }
+
+            if size == 1 {
+                let pivot_value = work[[k, k]];
+                d[[k, k]] = pivot_value;
+                if pivot_value != 0.0 {
+                    for i in k + 1..n {
+                        l[[i, k]] = work[[i, k]] / pivot_value;
+                    }
+                    for i in k + 1..n {
+                        for j in k + 1..n {
+                            work[[i, j]] -= l[[i, k]] * work[[k, j]];
+                        }
+                    }
+                }
+            } else {
+                let (d11, d21, d22) = (work[[k, k]], work[[k + 1, k]], work[[k + 1, k + 1]]);
+                d[[k, k]] = d11;
+                d[[k + 1, k]] = d21;
+                d[[k, k + 1]] = d21;
+                d[[k + 1, k + 1]] = d22;
+
+                let det = d11 * d22 - d21 * d21;
+                for i in k + 2..n {
+                    let (c1, c2) = (work[[i, k]], work[[i, k + 1]]);
+                    l[[i, k]] = (c1 * d22 - c2 * d21) / det;
+                    l[[i, k + 1]] = (c2 * d11 - c1 * d21) / det;
+                }
+                for i in k + 2..n {
+                    for j in k + 2..n {
+                        work[[i, j]] -= l[[i, k]] * work[[k, j]] + l[[i, k + 1]] * work[[k + 1, j]];
+                    }
+                }
+            }
+
+            blocks.push(size);
+            k += size;
+        }
+
+        Ok(LdltFactor {
+            l,
+            d,
+            blocks,
+            permutation,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.l.nrows()
+    }
+
+    /// Row `i` of PAP^T is row `permutation()[i]` of A, and likewise for columns.
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    pub fn p(&self) -> Matrix {
+        let n = self.size();
+        let mut p = Array2::<f64>::zeros((n, n));
+        for (i, &row) in self.permutation.iter().enumerate() {
+            p[[i, row]] = 1.0;
+        }
+        Matrix { data: p }
+    }
+
+    pub fn l(&self) -> Matrix {
+        Matrix {
+            data: self.l.clone(),
+        }
+    }
+
+    pub fn d(&self) -> Matrix {
+        Matrix {
+            data: self.d.clone(),
+        }
+    }
+
+    /// Sizes (1 or 2) of the diagonal blocks of D, in order.
+    pub fn block_sizes(&self) -> &[usize] {
+        &self.blocks
+    }
+
+    pub fn solve(&self, b: &Vector) -> Result<Vector, LinAlgError> {
+        if b.data.len() != self.size() {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: self.size(),
+                found: b.data.len(),
+            });
+        }
+        self.check_nonsingular()?;
+
+        Ok(Vector {
+            data: self.substitute(b.data.view()),
+        })
+    }
+
+    pub fn solve_matrix(&self, b: &Matrix) -> Result<Matrix, LinAlgError> {
+        if b.data.nrows() != self.size() {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: self.size(),
+                found: b.data.nrows(),
+            });
+        }
+        self.check_nonsingular()?;
+
+        Ok(Matrix {
+            data: solve_columns(&b.data, self.size(), |column| self.substitute(column)),
+        })
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let mut k = 0;
+        let mut det = 1.0;
+        for &size in &self.blocks {
+            det *= self.block_determinant(k, size);
+            k += size;
+        }
+        det
+    }
+
+    fn block_determinant(&self, k: usize, size: usize) -> f64 {
+        if size == 1 {
+            self.d[[k, k]]
+        } else {
+            self.d[[k, k]] * self.d[[k + 1, k + 1]] - self.d[[k + 1, k]] * self.d[[k + 1, k]]
+        }
+    }
+
+    fn check_nonsingular(&self) -> Result<(), LinAlgError> {
+        let mut k = 0;
+        for &size in &self.blocks {
+            if self.block_determinant(k, size) == 0.0 {
+                return Err(LinAlgError::Singular { pivot: k });
+            }
+            k += size;
+        }
+        Ok(())
+    }
+
+    fn substitute(&self, b: ArrayView1<f64>) -> Array1<f64> {
+        let n = self.size();
+        let mut x: Array1<f64> = self.permutation.iter().map(|&row| b[row]).collect();
+
+        for i in 0..n {
+            for j in 0..i {
+                x[i] -= self.l[[i, j]] * x[j];
+            }
+        }
+
+        let mut k = 0;
+        for &size in &self.blocks {
+            if size == 1 {
+                x[k] /= self.d[[k, k]];
+            } else {
+                let (d11, d21, d22) = (self.d[[k, k]], self.d[[k + 1, k]], self.d[[k + 1, k + 1]]);
+                let det = d11 * d22 - d21 * d21;
+                let (x1, x2) = (x[k], x[k + 1]);
+                x[k] = (d22 * x1 - d21 * x2) / det;
+                x[k + 1] = (d11 * x2 - d21 * x1) / det;
+            }
+            k += size;
+        }
+
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                x[i] -= self.l[[j, i]] * x[j];
+            }
+        }
+
+        let mut result = Array1::<f64>::zeros(n);
+        for (i, &row) in self.permutation.iter().enumerate() {
+            result[row] = x[i];
+        }
+
+        result
+    }
+}
+
+/// Householder QR factorization AP = QR of an m x n matrix, where P is the
+/// identity unless column pivoting was requested. R is stored in the upper
+/// triangle and the Householder vectors (with an implied leading 1) below the
+/// diagonal, one scaling factor per reflector in `tau`. Reflector j is
+/// H_j = I - tau_j v_j v_j^H, so Q = H_0 H_1 ... is orthogonal for real
+/// matrices and unitary for complex ones.
+#[derive(Clone)]
+pub struct QrFactor<T: Scalar = f64> {
+    qr: Array2<T>,
+    tau: Vec<T>,
+    permutation: Vec<usize>,
+    swaps: usize,
+    norm: T::Real,
+}
+
+impl<T: Scalar> QrFactor<T> {
+    pub(crate) fn new(a: &Array2<T>) -> QrFactor<T> {
+        QrFactor::factorize(a, false)
+    }
+
+    // At every step the remaining column with the largest norm is moved to the
+    // front, so the diagonal of R is non-increasing in magnitude.
+    pub(crate) fn new_pivoted(a: &Array2<T>) -> QrFactor<T> {
+        QrFactor::factorize(a, true)
+    }
+
+    fn factorize(a: &Array2<T>, pivoting: bool) -> QrFactor<T> {
+        let (m, n) = a.dim();
+        let mut qr = a.clone();
+        let mut tau = Vec::with_capacity(m.min(n));
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut swaps = 0;
+
+        for j in 0..m.min(n) {
+            if pivoting {
+                let column_norm = |qr: &Array2<T>, c: usize| -> T::Real {
+                    (j..m).map(|i| qr[[i, c]].modulus_squared()).sum()
+                };
+                let pivot = (j + 1..n).fold(j, |best, c| {
+                    if column_norm(&qr, c) > column_norm(&qr, best) {
+                        c
+                    } else {
+                        best
+                    }
+                });
+                if pivot != j {
+                    for i in 0..m {
+                        qr.swap([i, j], [i, pivot]);
+                    }
+                    permutation.swap(j, pivot);
+                    swaps += 1;
+                }
+            }
+
+            let sub_norm = (j + 1..m)
+                .map(|i| qr[[i, j]].modulus_squared())
+                .sum::<T::Real>()
+                .sqrt();
+            if sub_norm == T::Real::zero() {
+                tau.push(T::zero());
+                continue;
+            }
+
+            // beta is real, so the diagonal of R is real even for complex A.
+            let alpha = qr[[j, j]];
+            let beta = T::from_real(-alpha.real().signum() * alpha.modulus().hypot(sub_norm));
+            let scale = T::one() / (alpha - beta);
+            for i in j + 1..m {
+                qr[[i, j]] *= scale;
+            }
+            qr[[j, j]] = beta;
+            tau.push((beta - alpha) / beta);
+
+            // Apply H_j^H to the remaining columns.
+            for c in j + 1..n {
+                let s = tau[j].conj()
+                    * (qr[[j, c]]
+                        + (j + 1..m)
+                            .map(|i| qr[[i, j]].conj() * qr[[i, c]])
+                            .sum::<T>());
+                qr[[j, c]] -= s;
+                for i in j + 1..m {
+                    let update = s * qr[[i, j]];
+                    qr[[i, c]] -= update;
+                }
+            }
+        }
+
+        QrFactor {
+            qr,
+            tau,
+            permutation,
+            swaps,
+            norm: one_norm(a),
+        }
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.qr.nrows()
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.qr.ncols()
+    }
+
+    /// Column `j` of AP is column `permutation()[j]` of A.
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    pub fn p(&self) -> Matrix<T> {
+        let n = self.ncols();
+        let mut p = Array2::<T>::zeros((n, n));
+        for (j, &column) in self.permutation.iter().enumerate() {
+            p[[column, j]] = T::one();
+        }
+        Matrix { data: p }
+    }
+
+    /// Thin factor: the first min(m, n) columns of Q.
+    pub fn q(&self) -> Matrix<T> {
+        self.q_columns(self.tau.len())
+    }
+
+    /// Full m x m orthogonal (unitary) factor.
+    pub fn q_full(&self) -> Matrix<T> {
+        self.q_columns(self.nrows())
+    }
+
+    /// Thin factor: the first min(m, n) rows of R.
+    pub fn r(&self) -> Matrix<T> {
+        self.r_rows(self.tau.len())
+    }
+
+    /// Full m x n upper-trapezoidal factor.
+    pub fn r_full(&self) -> Matrix<T> {
+        self.r_rows(self.nrows())
+    }
+
+    fn q_columns(&self, columns: usize) -> Matrix<T> {
+        let m = self.nrows();
+        let mut q = Array2::<T>::zeros((m, columns));
+        for j in 0..columns {
+            let mut e = Array1::<T>::zeros(m);
+            e[j] = T::one();
+            self.apply_q(&mut e);
+            q.column_mut(j).assign(&e);
+        }
+        Matrix { data: q }
+    }
+
+    fn r_rows(&self, rows: usize) -> Matrix<T> {
+        let data = Array2::from_shape_fn((rows, self.ncols()), |(i, j)| {
+            if i <= j && i < self.tau.len() {
+                self.qr[[i, j]]
+            } else {
+                T::zero()
+            }
+        });
+        Matrix { data }
+    }
+
+    /// Least-squares solution of Ax = b for m >= n, exact when A is square.
+    pub fn solve(&self, b: &Vector<T>) -> Result<Vector<T>, LinAlgError> {
+        self.check_solvable()?;
+        if b.data.len() != self.nrows() {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: self.nrows(),
+                found: b.data.len(),
+            });
+        }
+
+        Ok(Vector {
+            data: self.substitute(b.data.view()),
+        })
+    }
+
+    pub fn solve_matrix(&self, b: &Matrix<T>) -> Result<Matrix<T>, LinAlgError> {
+        self.check_solvable()?;
+        if b.data.nrows() != self.nrows() {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: self.nrows(),
+                found: b.data.nrows(),
+            });
+        }
+
+        Ok(Matrix {
+            data: solve_columns(&b.data, self.ncols(), |column| self.substitute(column)),
+        })
+    }
+
+    /// Solves A^H x = b. For m > n this is underdetermined and the
+    /// minimum-norm solution is returned.
+    pub fn solve_conjugate_transpose(&self, b: &Vector<T>) -> Result<Vector<T>, LinAlgError> {
+        self.check_solvable()?;
+        if b.data.len() != self.ncols() {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: self.ncols(),
+                found: b.data.len(),
+            });
+        }
+
+        Ok(Vector {
+            data: self.substitute_adjoint(b.data.view()),
+        })
+    }
+
+    pub fn determinant(&self) -> Result<T, LinAlgError> {
+        self.check_square()?;
+
+        // det(H_j) = 1 - tau_j (1 + |v_j|^2), which is exactly -1 for a real
+        // reflector; every column swap contributes another factor of -1.
+        let m = self.nrows();
+        let mut determinant = self.qr.diag().iter().copied().product::<T>();
+        for (j, &tau) in self.tau.iter().enumerate() {
+            if tau != T::zero() {
+                let v_norm = (j + 1..m)
+                    .map(|i| self.qr[[i, j]].modulus_squared())
+                    .sum::<T::Real>();
+                determinant *= T::one() - tau * T::from_real(T::Real::one() + v_norm);
+            }
+        }
+        if self.swaps % 2 == 1 {
+            determinant = T::zero() - determinant;
+        }
+        Ok(determinant)
+    }
+
+    pub fn inverse(&self) -> Result<Matrix<T>, LinAlgError> {
+        self.check_square()?;
+        self.check_solvable()?;
+
+        Ok(Matrix {
+            data: solve_columns(&Array2::eye(self.nrows()), self.ncols(), |column| {
+                self.substitute(column)
+            }),
+        })
+    }
+
+    fn check_square(&self) -> Result<(), LinAlgError> {
+        if self.nrows() != self.ncols() {
+            return Err(LinAlgError::NotSquare {
+                rows: self.nrows(),
+                cols: self.ncols(),
+            });
+        }
+        Ok(())
+    }
+
+    fn check_solvable(&self) -> Result<(), LinAlgError> {
+        if self.nrows() < self.ncols() {
+            return Err(LinAlgError::Underdetermined {
+                rows: self.nrows(),
+                cols: self.ncols(),
+            });
+        }
+        if let Some(k) = self.qr.diag().iter().position(|&r| r == T::zero()) {
+            return Err(LinAlgError::Singular { pivot: k });
+        }
+        Ok(())
+    }
+
+    // Overwrites `x` with Q^H x.
+    fn apply_qh(&self, x: &mut Array1<T>) {
+        for j in 0..self.tau.len() {
+            self.reflect(j, self.tau[j].conj(), x);
+        }
+    }
+
+    // Overwrites `x` with Q x.
+    fn apply_q(&self, x: &mut Array1<T>) {
+        for j in (0..self.tau.len()).rev() {
+            self.reflect(j, self.tau[j], x);
+        }
+    }
+
+    // Overwrites `x` with (I - tau v_j v_j^H) x.
+    fn reflect(&self, j: usize, tau: T, x: &mut Array1<T>) {
+        let m = self.nrows();
+        if tau == T::zero() {
+            return;
+        }
+
+        let s = tau * (x[j] + (j + 1..m).map(|i| self.qr[[i, j]].conj() * x[i]).sum::<T>());
+        x[j] -= s;
+        for i in j + 1..m {
+            x[i] -= s * self.qr[[i, j]];
+        }
+    }
+
+    fn substitute(&self, b: ArrayView1<T>) -> Array1<T> {
+        let n = self.ncols();
+        let mut y = b.to_owned();
+        self.apply_qh(&mut y);
+
+        let mut z = Array1::<T>::zeros(n);
+        for i in (0..n).rev() {
+            let sum: T = (i + 1..n).map(|j| self.qr[[i, j]] * z[j]).sum();
+            z[i] = (y[i] - sum) / self.qr[[i, i]];
+        }
+
+        let mut x = Array1::<T>::zeros(n);
+        for (i, &column) in self.permutation.iter().enumerate() {
+            x[column] = z[i];
+        }
+
+        x
+    }
+
+    // A^H = P R^H Q^H, so permute b, solve R^H and apply Q.
+    fn substitute_adjoint(&self, b: ArrayView1<T>) -> Array1<T> {
+        let n = self.ncols();
+        let mut x = Array1::<T>::zeros(self.nrows());
+        for i in 0..n {
+            let sum: T = (0..i).map(|j| self.qr[[j, i]].conj() * x[j]).sum();
+            x[i] = (b[self.permutation[i]] - sum) / self.qr[[i, i]].conj();
+        }
+
+        self.apply_q(&mut x);
+        x
+    }
+}
+
+impl QrFactor<f64> {
+    /// Solves A^T x = b. For m > n this is underdetermined and the
+    /// minimum-norm solution is returned.
+    pub fn solve_transpose(&self, b: &Vector) -> Result<Vector, LinAlgError> {
+        self.solve_conjugate_transpose(b)
+    }
+
+    pub fn rcond(&self) -> Result<f64, LinAlgError> {
+        self.check_square()?;
+        if self.check_solvable().is_err() {
+            return Ok(0.0);
+        }
+
+        Ok(reciprocal_condition(
+            self.norm,
+            self.nrows(),
+            |x| self.substitute(x),
+            |x| self.substitute_adjoint(x),
+        ))
+    }
+}
+
+// Largest absolute column sum, i.e. the induced 1-norm.
+pub(crate) fn one_norm<T: Scalar>(a: &Array2<T>) -> T::Real {
+    a.axis_iter(Axis(1))
+        .map(|column| column.iter().map(|x| x.modulus()).sum::<T::Real>())
+        .fold(T::Real::zero(), nan_max)
+}
+
+// No entry is NaN or infinite, in either component for complex scalars.
+pub(crate) fn all_finite<T: Scalar>(a: &Array2<T>) -> bool {
+    a.iter().all(|x| x.modulus().is_finite())
+}
+
+// A equals its conjugate transpose to a relative tolerance; for real matrices
+// this is plain symmetry.
+pub(crate) fn is_hermitian<T: Scalar>(a: &Array2<T>) -> bool {
+    let n = a.nrows();
+    let tolerance = <T::Real as NumCast>::from(1e-12).unwrap();
+    (0..n).all(|i| {
+        (0..=i).all(|j| {
+            let (x, y) = (a[[i, j]], a[[j, i]]);
+            (x - y.conj()).modulus() <= tolerance * x.modulus().max(y.modulus()).max(T::Real::one())
+        })
+    })
+}
+
+// Solves one system per column of `b`, in parallel for large systems. Each solution has `rows`
+// entries.
+fn solve_columns<T, F>(b: &Array2<T>, rows: usize, solve: F) -> Array2<T>
+where
+    T: Scalar,
+    F: Fn(ArrayView1<T>) -> Array1<T> + Sync,
+{
+    let columns: Vec<Array1<T>> = parallel::run(b.ncols() * rows * rows, |min_len| {
+        b.axis_iter(Axis(1))
+            .into_par_iter()
+            .with_min_len(min_len)
+            .map(&solve)
+            .collect()
+    });
+
+    let mut result = Array2::<T>::zeros((rows, b.ncols()));
+    for (j, column) in columns.into_iter().enumerate() {
+        result.column_mut(j).assign(&column);
+    }
+
+    result
+}
+
+// Hager's estimate of ||A^-1||_1 with Higham's extra test vector, needing only
+// a handful of solves with A and A^T.
+fn estimate_inverse_norm<F, G>(n: usize, solve: F, solve_transpose: G) -> f64
+where
+    F: Fn(ArrayView1<f64>) -> Array1<f64>,
+    G: Fn(ArrayView1<f64>) -> Array1<f64>,
+{
+    if n == 0 {
+        return 0.0;
+    }
+
+    let mut x = Array1::<f64>::from_elem(n, 1.0 / n as f64);
+    let mut estimate = 0.0;
+    let mut last_index = None;
+
+    for _ in 0..5 {
+        let y = solve(x.view());
+        estimate = y.iter().map(|v| v.abs()).sum::<f64>();
+
+        let signs = y.mapv(|v| if v >= 0.0 { 1.0 } else { -1.0 });
+        let z = solve_transpose(signs.view());
+        let index = (0..n).fold(
+            0,
+            |best, i| if z[i].abs() > z[best].abs() { i } else { best },
+        );
+
+        if z[index].abs() <= z.dot(&x) || last_index == Some(index) {
+            break;
+        }
+
+        x.fill(0.0);
+        x[index] = 1.0;
+        last_index = Some(index);
+    }
+
+    let alternating: Array1<f64> = (0..n)
+        .map(|i| {
+            let magnitude = 1.0 + i as f64 / (n.max(2) - 1) as f64;
+            if i % 2 == 0 {
+                magnitude
+            } else {
+                -magnitude
+            }
+        })
+        .collect();
+    let alternate_estimate = 2.0
+        * solve(alternating.view())
+            .iter()
+            .map(|v| v.abs())
+            .sum::<f64>()
+        / (3 * n) as f64;
+
+    estimate.max(alternate_estimate)
+}
+
+fn reciprocal_condition<F, G>(norm: f64, n: usize, solve: F, solve_transpose: G) -> f64
+where
+    F: Fn(ArrayView1<f64>) -> Array1<f64>,
+    G: Fn(ArrayView1<f64>) -> Array1<f64>,
+{
+    if n == 0 {
+        return 1.0;
+    }
+
+    let inverse_norm = estimate_inverse_norm(n, solve, solve_transpose);
+    if norm == 0.0 || inverse_norm == 0.0 {
+        return 0.0;
+    }
+
+    1.0 / (norm * inverse_norm)
+}
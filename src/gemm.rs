@@ -0,0 +1,102 @@
+//! Matrix multiplication C = A B. The packed, register-blocked kernels come
+//! from matrixmultiply through ndarray's `general_mat_mul`, which selects SIMD
+//! micro-kernels for f32, f64, Complex32 and Complex64 at runtime. Rayon splits
+//! C into one horizontal band per worker; the bands are disjoint and written in
+//! place, so no per-row buffers or copies are needed. Small products run
+//! serially according to the crate's `ParallelConfig`.
+use crate::{parallel, Scalar};
+use ndarray::linalg::general_mat_mul;
+use ndarray::{s, Array2, ArrayView2, Axis};
+use rayon::prelude::*;
+
+// Bands thinner than this repack B for too little work to pay off.
+const MIN_BAND_ROWS: usize = 64;
+
+pub(crate) fn gemm<T: Scalar>(a: ArrayView2<T>, b: ArrayView2<T>) -> Array2<T> {
+    let (m, k) = a.dim();
+    let n = b.ncols();
+    let mut c = Array2::<T>::zeros((m, n));
+    if m == 0 || n == 0 || k == 0 {
+        return c;
+    }
+
+    parallel::run(m * k * n, |min_len| {
+        let bands = if min_len == 1 {
+            rayon::current_num_threads()
+        } else {
+            1
+        };
+        let rows = m.div_ceil(bands).max(MIN_BAND_ROWS);
+        c.axis_chunks_iter_mut(Axis(0), rows)
+            .into_par_iter()
+            .zip(a.axis_chunks_iter(Axis(0), rows))
+            .with_min_len(min_len)
+            .for_each(|(mut c_band, a_band)| {
+                general_mat_mul(T::one(), &a_band, &b, T::zero(), &mut c_band)
+            })
+    });
+
+    c
+}
+
+// Strassen's algorithm: seven half-size products instead of eight. Recursion
+// stops once any dimension is at or below `cutoff`, where the blocked kernel
+// is faster. Odd dimensions are padded with a zero row or column at each
+// level, so rectangular and non-power-of-two sizes work unchanged.
+pub(crate) fn strassen<T: Scalar>(a: ArrayView2<T>, b: ArrayView2<T>, cutoff: usize) -> Array2<T> {
+    let (m, k) = a.dim();
+    let n = b.ncols();
+    if m.min(k).min(n) <= cutoff.max(1) {
+        return gemm(a, b);
+    }
+
+    if m % 2 == 1 || k % 2 == 1 || n % 2 == 1 {
+        let a = pad(a, m + m % 2, k + k % 2);
+        let b = pad(b, k + k % 2, n + n % 2);
+        return strassen(a.view(), b.view(), cutoff)
+            .slice(s![..m, ..n])
+            .to_owned();
+    }
+
+    let (m2, k2, n2) = (m / 2, k / 2, n / 2);
+    let a11 = a.slice(s![..m2, ..k2]);
+    let a12 = a.slice(s![..m2, k2..]);
+    let a21 = a.slice(s![m2.., ..k2]);
+    let a22 = a.slice(s![m2.., k2..]);
+    let b11 = b.slice(s![..k2, ..n2]);
+    let b12 = b.slice(s![..k2, n2..]);
+    let b21 = b.slice(s![k2.., ..n2]);
+    let b22 = b.slice(s![k2.., n2..]);
+
+    let operands = vec![
+        (&a11 + &a22, &b11 + &b22),
+        (&a21 + &a22, b11.to_owned()),
+        (a11.to_owned(), &b12 - &b22),
+        (a22.to_owned(), &b21 - &b11),
+        (&a11 + &a12, b22.to_owned()),
+        (&a21 - &a11, &b11 + &b12),
+        (&a12 - &a22, &b21 + &b22),
+    ];
+    let p: Vec<Array2<T>> = parallel::run(m * k * n, |min_len| {
+        operands
+            .into_par_iter()
+            .with_min_len(min_len)
+            .map(|(x, y)| strassen(x.view(), y.view(), cutoff))
+            .collect()
+    });
+
+    let mut c = Array2::<T>::zeros((m, n));
+    c.slice_mut(s![..m2, ..n2])
+        .assign(&(&p[0] + &p[3] - &p[4] + &p[6]));
+    c.slice_mut(s![..m2, n2..]).assign(&(&p[2] + &p[4]));
+    c.slice_mut(s![m2.., ..n2]).assign(&(&p[1] + &p[3]));
+    c.slice_mut(s![m2.., n2..])
+        .assign(&(&p[0] - &p[1] + &p[2] + &p[5]));
+    c
+}
+
+fn pad<T: Scalar>(x: ArrayView2<T>, rows: usize, cols: usize) -> Array2<T> {
+    let mut padded = Array2::<T>::zeros((rows, cols));
+    padded.slice_mut(s![..x.nrows(), ..x.ncols()]).assign(&x);
+    padded
+}
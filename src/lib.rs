@@ -1,77 +1,135 @@
-use ndarray::{Array1, Array2};
+use ndarray::{Array1, Array2, Zip};
+use num_traits::{Float, NumCast, One, Zero};
 use rayon::prelude::*;
+use scalar::{nan_max, nan_min};
+
+mod display;
+mod eigen;
+mod error;
+mod factorization;
+mod gemm;
+mod macros;
+mod ops;
+mod parallel;
+mod scalar;
+mod svd;
+
+pub use eigen::EigenDecomposition;
+pub use error::LinAlgError;
+pub use factorization::{CholeskyFactor, LdltFactor, LuFactor, QrFactor};
+pub use num_complex::{Complex32, Complex64};
+pub use parallel::{
+    parallel_config, set_parallel_config, ParallelConfig, DEFAULT_PARALLEL_THRESHOLD,
+};
+pub use scalar::Scalar;
+pub use svd::{LeastSquares, Svd};
 
-pub struct Matrix {
-    pub data: Array2<f64>,
+#[derive(Clone)]
+pub struct Matrix<T: Scalar = f64> {
+    pub data: Array2<T>,
 }
-impl Matrix {
-    pub fn add(&self, other: &Matrix) -> Result<Matrix, String> {
-        if self.data.dim() != other.data.dim() {
-            return Err("Matrices must be of the same dimensions".to_string());
+impl<T: Scalar> Matrix<T> {
+    pub fn add(&self, other: &Matrix<T>) -> Result<Matrix<T>, LinAlgError> {
+        let (rows, cols) = self.data.dim();
+        let (other_rows, other_cols) = other.data.dim();
+        if rows != other_rows {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: rows,
+                found: other_rows,
+            });
+        }
+        if cols != other_cols {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: cols,
+                found: other_cols,
+            });
         }
 
         let sum_data = &self.data + &other.data;
         Ok(Matrix { data: sum_data })
     }
 
-    pub fn multiply(&self, other: &Matrix) -> Result<Matrix, String> {
+    pub fn multiply(&self, other: &Matrix<T>) -> Result<Matrix<T>, LinAlgError> {
         if self.data.ncols() != other.data.nrows() {
-            return Err("Inner matrix dimensions must match for multiplication".to_string());
+            return Err(LinAlgError::DimensionMismatch {
+                expected: self.data.ncols(),
+                found: other.data.nrows(),
+            });
         }
 
-        let n = self.data.nrows();
-        let m = other.data.ncols();
-        let p = self.data.ncols();
-
-        let partial_results: Vec<Array2<f64>> = (0..n)
-            .into_par_iter()
-            .map(|i| {
-                let mut row_result = Array2::<f64>::zeros((1, m));
-                for j in 0..m {
-                    row_result[[0, j]] =
-                        (0..p).map(|k| self.data[[i, k]] * other.data[[k, j]]).sum();
-                }
-                row_result
-            })
-            .collect();
+        Ok(Matrix {
+            data: gemm::gemm(self.data.view(), other.data.view()),
+        })
+    }
 
-        let mut result = Array2::<f64>::zeros((n, m));
-        for (i, partial) in partial_results.into_iter().enumerate() {
-            result.row_mut(i).assign(&partial.row(0));
+    /// Strassen multiplication for large products. Subproblems with any
+    /// dimension at or below `cutoff` use `multiply`; a cutoff of a few hundred
+    /// usually works best. Opt-in because the rounding error grows faster than
+    /// for the classic product.
+    pub fn multiply_strassen(
+        &self,
+        other: &Matrix<T>,
+        cutoff: usize,
+    ) -> Result<Matrix<T>, LinAlgError> {
+        if self.data.ncols() != other.data.nrows() {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: self.data.ncols(),
+                found: other.data.nrows(),
+            });
         }
 
-        Ok(Matrix { data: result })
+        Ok(Matrix {
+            data: gemm::strassen(self.data.view(), other.data.view(), cutoff),
+        })
     }
 
-    pub fn transpose(&self) -> Matrix {
+    pub fn transpose(&self) -> Matrix<T> {
         Matrix {
             data: self.data.t().to_owned(),
         }
     }
 
-    pub fn determinant(&self) -> Option<f64> {
+    /// Hermitian (conjugate) transpose A^H. Same as `transpose` for real matrices.
+    pub fn conjugate_transpose(&self) -> Matrix<T> {
+        Matrix {
+            data: self.data.t().mapv(|x| x.conj()),
+        }
+    }
+
+    pub fn determinant(&self) -> Result<T, LinAlgError> {
+        match self.lu_factor() {
+            Ok(lu) => Ok(lu.determinant()),
+            Err(LinAlgError::Singular { .. }) => Ok(T::zero()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Laplace expansion along the first row. Exponential in the size of the
+    /// matrix, so only useful for small matrices where the exact sum of products
+    /// matters more than speed.
+    pub fn determinant_cofactor(&self) -> Result<T, LinAlgError> {
         let (rows, cols) = self.data.dim();
         if rows != cols {
-            return None; // Not a square matrix
+            return Err(LinAlgError::NotSquare { rows, cols });
         }
 
-        Some(self.calculate_determinant(&self.data))
+        Ok(self.calculate_determinant(&self.data))
     }
 
-    pub(self) fn calculate_determinant(&self, matrix: &Array2<f64>) -> f64 {
+    pub(self) fn calculate_determinant(&self, matrix: &Array2<T>) -> T {
         let (rows, _) = matrix.dim();
 
         if rows == 1 {
             return matrix[[0, 0]];
         }
 
-        let mut determinant = 0.0;
-        let mut sign = 1.0;
+        let mut determinant = T::zero();
+        let mut sign = T::one();
 
         for col in 0..rows {
             let minor = self.create_minor(matrix, 0, col);
             determinant += sign * matrix[[0, col]] * self.calculate_determinant(&minor);
-            sign *= -1.0;
+            sign = T::zero() - sign;
         }
 
         determinant
@@ -79,12 +137,12 @@ impl Matrix {
 
     pub(self) fn create_minor(
         &self,
-        matrix: &Array2<f64>,
+        matrix: &Array2<T>,
         row_to_exclude: usize,
         col_to_exclude: usize,
-    ) -> Array2<f64> {
+    ) -> Array2<T> {
         let (rows, cols) = matrix.dim();
-        let mut minor = Array2::<f64>::zeros((rows - 1, cols - 1));
+        let mut minor = Array2::<T>::zeros((rows - 1, cols - 1));
 
         let mut minor_row = 0;
         let mut minor_col;
@@ -106,74 +164,110 @@ impl Matrix {
         minor
     }
 
-    pub fn identity(size: usize) -> Matrix {
-        let mut data = Array2::<f64>::zeros((size, size));
+    pub fn identity(size: usize) -> Matrix<T> {
+        let mut data = Array2::<T>::zeros((size, size));
         for i in 0..size {
-            data[[i, i]] = 1.0;
+            data[[i, i]] = T::one();
         }
         Matrix { data }
     }
 
-    pub fn zero(rows: usize, cols: usize) -> Matrix {
-        let data = Array2::<f64>::zeros((rows, cols));
+    pub fn zero(rows: usize, cols: usize) -> Matrix<T> {
+        let data = Array2::<T>::zeros((rows, cols));
         Matrix { data }
     }
 
-    pub fn inverse(&self) -> Result<Matrix, String> {
-        let (rows, cols) = self.data.dim();
+    pub fn ones(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix::filled(rows, cols, T::one())
+    }
 
-        if rows != cols {
-            return Err("Only square matrices can be inverted".to_string());
-        }
+    pub fn filled(rows: usize, cols: usize, value: T) -> Matrix<T> {
+        let data = Array2::<T>::from_elem((rows, cols), value);
+        Matrix { data }
+    }
 
-        let det = self.determinant().unwrap_or(0.0);
+    pub fn from_fn<F>(rows: usize, cols: usize, mut f: F) -> Matrix<T>
+    where
+        F: FnMut(usize, usize) -> T,
+    {
+        let data = Array2::<T>::from_shape_fn((rows, cols), |(i, j)| f(i, j));
+        Matrix { data }
+    }
+
+    pub fn from_diag(diagonal: &Vector<T>) -> Matrix<T> {
+        let data = Array2::<T>::from_diag(&diagonal.data);
+        Matrix { data }
+    }
 
-        if det == 0.0 {
-            return Err("Matrix is not invertible".to_string());
+    /// Every row must have the same length as the first; an empty slice gives
+    /// a 0x0 matrix.
+    pub fn from_rows<R: AsRef<[T]>>(rows: &[R]) -> Result<Matrix<T>, LinAlgError> {
+        let cols = rows.first().map_or(0, |row| row.as_ref().len());
+        for row in rows {
+            if row.as_ref().len() != cols {
+                return Err(LinAlgError::DimensionMismatch {
+                    expected: cols,
+                    found: row.as_ref().len(),
+                });
+            }
         }
 
-        let cofactors: Vec<_> = (0..rows)
-            .into_par_iter()
-            .flat_map(|i| {
-                (0..cols).into_par_iter().map(move |j| {
-                    let minor = self.create_minor(&self.data, i, j);
-                    let cofactor =
-                        self.calculate_determinant(&minor) * (-1.0f64).powi((i + j) as i32);
-                    (i, j, cofactor)
-                })
-            })
-            .collect();
+        Ok(Matrix::from_fn(rows.len(), cols, |i, j| {
+            rows[i].as_ref()[j]
+        }))
+    }
 
-        let mut adjugate = Array2::<f64>::zeros((rows, cols));
-        for (i, j, cofactor) in cofactors {
-            adjugate[[j, i]] = cofactor;
+    pub fn from_cols<C: AsRef<[T]>>(cols: &[C]) -> Result<Matrix<T>, LinAlgError> {
+        let rows = cols.first().map_or(0, |col| col.as_ref().len());
+        for col in cols {
+            if col.as_ref().len() != rows {
+                return Err(LinAlgError::DimensionMismatch {
+                    expected: rows,
+                    found: col.as_ref().len(),
+                });
+            }
         }
 
-        let inverse_data = adjugate.mapv(|x| x / det);
+        Ok(Matrix::from_fn(rows, cols.len(), |i, j| {
+            cols[j].as_ref()[i]
+        }))
+    }
+
+    pub fn inverse(&self) -> Result<Matrix<T>, LinAlgError> {
+        Ok(self.lu_factor()?.inverse())
+    }
+
+    pub fn solve(&self, b: &Vector<T>) -> Result<Vector<T>, LinAlgError> {
+        self.lu_factor()?.solve(b)
+    }
 
-        Ok(Matrix { data: inverse_data })
+    pub fn solve_matrix(&self, b: &Matrix<T>) -> Result<Matrix<T>, LinAlgError> {
+        self.lu_factor()?.solve_matrix(b)
     }
 
-    pub fn lu_decomposition(&self) -> Result<(Matrix, Matrix), String> {
+    pub fn lu_decomposition(&self) -> Result<(Matrix<T>, Matrix<T>), LinAlgError> {
         let n = self.data.nrows();
         if n != self.data.ncols() {
-            return Err("Matrix must be square".to_string());
+            return Err(LinAlgError::NotSquare {
+                rows: n,
+                cols: self.data.ncols(),
+            });
         }
 
-        let mut lower = Array2::<f64>::zeros((n, n));
-        let mut upper = Array2::<f64>::zeros((n, n));
+        let mut lower = Array2::<T>::zeros((n, n));
+        let mut upper = Array2::<T>::zeros((n, n));
 
         for i in 0..n {
             for k in i..n {
-                let sum = (0..i).fold(0.0, |sum, j| sum + lower[[i, j]] * upper[[j, k]]);
+                let sum = (0..i).fold(T::zero(), |sum, j| sum + lower[[i, j]] * upper[[j, k]]);
                 upper[[i, k]] = self.data[[i, k]] - sum;
             }
 
             for k in i..n {
                 if i == k {
-                    lower[[i, i]] = 1.0;
+                    lower[[i, i]] = T::one();
                 } else {
-                    let sum = (0..i).fold(0.0, |sum, j| sum + lower[[k, j]] * upper[[j, i]]);
+                    let sum = (0..i).fold(T::zero(), |sum, j| sum + lower[[k, j]] * upper[[j, i]]);
                     lower[[k, i]] = (self.data[[k, i]] - sum) / upper[[i, i]];
                 }
             }
@@ -182,155 +276,702 @@ impl Matrix {
         Ok((Matrix { data: lower }, Matrix { data: upper }))
     }
 
-    pub fn l1_norm(&self) -> f64 {
-        self.data.iter().map(|&x| x.abs()).sum()
+    /// Reduced row echelon form by Gauss-Jordan elimination with partial
+    /// pivoting, and the indices of the pivot columns. Entries within
+    /// eps * max(m, n) * max|a_ij| of zero are treated as zero; pivot columns
+    /// are set to exact unit vectors and negligible rows to exact zeros.
+    pub fn rref(&self) -> (Matrix<T>, Vec<usize>) {
+        let (m, n) = self.data.dim();
+        let mut r = self.data.clone();
+        let largest = r.iter().map(|x| x.modulus()).fold(T::Real::zero(), nan_max);
+        let tolerance =
+            T::Real::epsilon() * <T::Real as NumCast>::from(m.max(n)).unwrap() * largest;
+
+        let mut pivots = Vec::new();
+        let mut row = 0;
+        for col in 0..n {
+            if row == m {
+                break;
+            }
+
+            let pivot_row = (row..m).fold(row, |best, i| {
+                if r[[i, col]].modulus() > r[[best, col]].modulus() {
+                    i
+                } else {
+                    best
+                }
+            });
+            if r[[pivot_row, col]].modulus() <= tolerance {
+                for i in row..m {
+                    r[[i, col]] = T::zero();
+                }
+                continue;
+            }
+
+            for j in col..n {
+                r.swap([row, j], [pivot_row, j]);
+            }
+            let pivot = r[[row, col]];
+            for j in col..n {
+                r[[row, j]] /= pivot;
+            }
+            r[[row, col]] = T::one();
+
+            for i in (0..m).filter(|&i| i != row) {
+                let factor = r[[i, col]];
+                if factor == T::zero() {
+                    continue;
+                }
+                for j in col..n {
+                    let update = factor * r[[row, j]];
+                    r[[i, j]] -= update;
+                }
+                r[[i, col]] = T::zero();
+            }
+
+            pivots.push(col);
+            row += 1;
+        }
+
+        (Matrix { data: r }, pivots)
+    }
+
+    pub fn plu_decomposition(&self) -> Result<PluDecomposition<T>, LinAlgError> {
+        let lu = self.lu_factor()?;
+
+        Ok(PluDecomposition {
+            permutation: lu.permutation().to_vec(),
+            p: lu.p(),
+            l: lu.l(),
+            u: lu.u(),
+            swaps: lu.swaps(),
+        })
     }
 
-    pub fn l2_norm(&self) -> f64 {
-        self.data.iter().map(|&x| x * x).sum::<f64>().sqrt()
+    pub fn lu_factor(&self) -> Result<LuFactor<T>, LinAlgError> {
+        LuFactor::new(&self.data)
     }
 
-    pub fn infinity_norm(&self) -> f64 {
+    pub fn qr_factor(&self) -> QrFactor<T> {
+        QrFactor::new(&self.data)
+    }
+
+    pub fn qr_factor_pivoted(&self) -> QrFactor<T> {
+        QrFactor::new_pivoted(&self.data)
+    }
+
+    /// Thin QR: Q is m x min(m, n) with orthonormal columns, R is min(m, n) x n.
+    pub fn qr(&self) -> (Matrix<T>, Matrix<T>) {
+        let qr = self.qr_factor();
+        (qr.q(), qr.r())
+    }
+
+    /// Full QR: Q is m x m orthogonal (unitary for complex A), R is m x n.
+    pub fn qr_full(&self) -> (Matrix<T>, Matrix<T>) {
+        let qr = self.qr_factor();
+        (qr.q_full(), qr.r_full())
+    }
+
+    /// Thin QR with column pivoting, satisfying A[:, permutation] = QR. The
+    /// diagonal of R is non-increasing in magnitude, which reveals the rank.
+    pub fn qr_pivoted(&self) -> (Matrix<T>, Matrix<T>, Vec<usize>) {
+        let qr = self.qr_factor_pivoted();
+        (qr.q(), qr.r(), qr.permutation().to_vec())
+    }
+
+    /// Entrywise 1-norm, the sum of |a_ij|. This is not the induced 1-norm
+    /// (maximum column sum); use `norm(NormKind::One)` for that.
+    pub fn l1_norm(&self) -> T::Real {
+        self.data.iter().map(|x| x.modulus()).sum()
+    }
+
+    /// Entrywise 2-norm, i.e. the Frobenius norm. This is not the spectral
+    /// norm; use `norm(NormKind::Spectral)` for that.
+    pub fn l2_norm(&self) -> T::Real {
+        self.data
+            .iter()
+            .map(|x| x.modulus_squared())
+            .sum::<T::Real>()
+            .sqrt()
+    }
+
+    /// Induced infinity-norm, the maximum absolute row sum.
+    pub fn infinity_norm(&self) -> T::Real {
         self.data
             .axis_iter(ndarray::Axis(0))
-            .map(|row| row.iter().map(|&x| x.abs()).sum::<f64>())
-            .max_by(|x, y| x.partial_cmp(y).unwrap())
-            .unwrap_or(0.0)
+            .map(|row| row.iter().map(|x| x.modulus()).sum::<T::Real>())
+            .fold(T::Real::zero(), nan_max)
     }
 
-    pub fn trace(&self) -> f64 {
-        self.data.diag().iter().sum()
+    pub fn trace(&self) -> T {
+        self.data.diag().iter().copied().sum()
     }
 
-    pub fn eigenvector(&self, max_iters: usize, tolerance: f64) -> Result<Vector, String> {
+    /// All eigenvalues of a Hermitian matrix in increasing order, and a matrix
+    /// whose orthonormal columns are the corresponding eigenvectors. For real
+    /// matrices this is the same as `symmetric_eigen`.
+    pub fn hermitian_eigen(&self) -> Result<(Vector<T::Real>, Matrix<T>), LinAlgError> {
+        let (values, vectors) = eigen::hermitian_eigen(&self.data)?;
+        Ok((Vector { data: values }, Matrix { data: vectors }))
+    }
+
+    pub fn kronecker_product(&self, other: &Matrix<T>) -> Matrix<T> {
+        let (a_rows, a_cols) = self.data.dim();
+        let (b_rows, b_cols) = other.data.dim();
+
+        let mut result = Array2::<T>::zeros((a_rows * b_rows, a_cols * b_cols));
+
+        for a_row in 0..a_rows {
+            for a_col in 0..a_cols {
+                for b_row in 0..b_rows {
+                    for b_col in 0..b_cols {
+                        result[[a_row * b_rows + b_row, a_col * b_cols + b_col]] = 
+                            self.data[[a_row, a_col]] * other.data[[b_row, b_col]];
+                    }
+                }
+            }
+        }
+
+        Matrix { data: result }
+    }
+
+
+}
+
+impl Matrix {
+    pub fn cholesky_factor(&self) -> Result<CholeskyFactor, LinAlgError> {
+        CholeskyFactor::new(&self.data)
+    }
+
+    /// Lower-triangular L with A = LL^T for symmetric positive-definite A.
+    pub fn cholesky(&self) -> Result<Matrix, LinAlgError> {
+        Ok(self.cholesky_factor()?.l())
+    }
+
+    pub fn ldlt(&self) -> Result<LdltFactor, LinAlgError> {
+        LdltFactor::new(&self.data)
+    }
+
+    /// Thin SVD: for an m x n matrix with k = min(m, n), U is m x k and V^T is
+    /// k x n. Pass `false` to skip computing either set of singular vectors.
+    pub fn svd(&self, compute_u: bool, compute_v: bool) -> Result<Svd, LinAlgError> {
+        svd::svd(&self.data, compute_u, compute_v, false)
+    }
+
+    /// Full SVD: U is m x m and V^T is n x n.
+    pub fn svd_full(&self, compute_u: bool, compute_v: bool) -> Result<Svd, LinAlgError> {
+        svd::svd(&self.data, compute_u, compute_v, true)
+    }
+
+    pub fn singular_values(&self) -> Result<Vector, LinAlgError> {
+        Ok(self.svd(false, false)?.singular_values)
+    }
+
+    /// Matrix norm of the given kind, following numpy.linalg.norm. Only the
+    /// spectral and nuclear norms need an SVD and can fail to converge.
+    pub fn norm(&self, kind: NormKind) -> Result<f64, LinAlgError> {
+        Ok(match kind {
+            NormKind::Frobenius => self.l2_norm(),
+            NormKind::One => factorization::one_norm(&self.data),
+            NormKind::Infinity => self.infinity_norm(),
+            // The SVD rejects non-finite input; NaN wins over infinity as in
+            // the other norms.
+            NormKind::Spectral | NormKind::Nuclear if self.data.iter().any(|x| x.is_nan()) => {
+                f64::NAN
+            }
+            NormKind::Spectral | NormKind::Nuclear if self.data.iter().any(|x| x.is_infinite()) => {
+                f64::INFINITY
+            }
+            NormKind::Spectral => self.singular_values()?.data.get(0).copied().unwrap_or(0.0),
+            NormKind::Nuclear => self.singular_values()?.data.sum(),
+            NormKind::MaxAbs => self.data.iter().fold(0.0, |max, &x| nan_max(max, x.abs())),
+        })
+    }
+
+    /// Numerical rank: the number of singular values above `tol`. `None` uses
+    /// s_max * max(m, n) * eps, as numpy.linalg.matrix_rank does.
+    pub fn rank(&self, tol: Option<f64>) -> Result<usize, LinAlgError> {
+        svd::rank(&self.data, tol)
+    }
+
+    /// Orthonormal bases, as matrix columns, for the four fundamental subspaces,
+    /// all taken from one SVD with the same rank tolerance as `rank`. A basis
+    /// of a trivial subspace has zero columns.
+    pub fn column_space(&self, tol: Option<f64>) -> Result<Matrix, LinAlgError> {
+        let (rank, u, _) = svd::subspaces(&self.data, tol)?;
+        Ok(Matrix {
+            data: u.slice(ndarray::s![.., ..rank]).to_owned(),
+        })
+    }
+
+    pub fn left_null_space(&self, tol: Option<f64>) -> Result<Matrix, LinAlgError> {
+        let (rank, u, _) = svd::subspaces(&self.data, tol)?;
+        Ok(Matrix {
+            data: u.slice(ndarray::s![.., rank..]).to_owned(),
+        })
+    }
+
+    pub fn row_space(&self, tol: Option<f64>) -> Result<Matrix, LinAlgError> {
+        let (rank, _, v) = svd::subspaces(&self.data, tol)?;
+        Ok(Matrix {
+            data: v.slice(ndarray::s![.., ..rank]).to_owned(),
+        })
+    }
+
+    pub fn null_space(&self, tol: Option<f64>) -> Result<Matrix, LinAlgError> {
+        let (rank, _, v) = svd::subspaces(&self.data, tol)?;
+        Ok(Matrix {
+            data: v.slice(ndarray::s![.., rank..]).to_owned(),
+        })
+    }
+
+    /// Condition number ||A|| ||A^-1|| in the given norm, as in
+    /// numpy.linalg.cond. The spectral condition number s_max / s_min comes from
+    /// the SVD and is defined for any shape; the other norms need a square
+    /// matrix and form the inverse. Singular matrices give infinity.
+    pub fn cond(&self, norm: NormKind) -> Result<f64, LinAlgError> {
+        if norm == NormKind::Spectral {
+            let s = self.singular_values()?.data;
+            let (largest, smallest) = match (s.first(), s.last()) {
+                (Some(&largest), Some(&smallest)) => (largest, smallest),
+                _ => return Ok(1.0),
+            };
+            return Ok(if smallest == 0.0 {
+                f64::INFINITY
+            } else {
+                largest / smallest
+            });
+        }
+
+        let (rows, cols) = self.data.dim();
+        if rows != cols {
+            return Err(LinAlgError::NotSquare { rows, cols });
+        }
+        match self.inverse() {
+            Ok(inverse) => Ok(self.norm(norm)? * inverse.norm(norm)?),
+            Err(LinAlgError::Singular { .. }) => Ok(f64::INFINITY),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Estimate of the reciprocal 1-norm condition number from an LU
+    /// factorization, costing O(n^2) beyond the factorization itself. Zero for
+    /// exactly singular matrices; see `LuFactor::rcond`.
+    pub fn rcond_estimate(&self) -> Result<f64, LinAlgError> {
+        if !factorization::all_finite(&self.data) {
+            return Err(LinAlgError::NonFinite);
+        }
+        match self.lu_factor() {
+            Ok(lu) => Ok(lu.rcond()),
+            Err(LinAlgError::Singular { .. }) => Ok(0.0),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// `solve` and `inverse` that refuse matrices whose estimated reciprocal
+    /// condition number is below `min_rcond` (f64::EPSILON is a common choice),
+    /// returning `IllConditioned` instead of a meaningless result.
+    pub fn solve_checked(&self, b: &Vector, min_rcond: f64) -> Result<Vector, LinAlgError> {
+        self.well_conditioned_lu(min_rcond)?.solve(b)
+    }
+
+    pub fn inverse_checked(&self, min_rcond: f64) -> Result<Matrix, LinAlgError> {
+        Ok(self.well_conditioned_lu(min_rcond)?.inverse())
+    }
+
+    /// Moore-Penrose pseudoinverse of any shape of matrix. Singular values at
+    /// or below rcond * s_max are treated as zero; `None` uses the same default
+    /// cutoff as `lstsq`.
+    pub fn pinv(&self, rcond: Option<f64>) -> Result<Matrix, LinAlgError> {
+        Ok(Matrix {
+            data: svd::pinv(&self.data, rcond)?,
+        })
+    }
+
+    /// Least-squares solution of Ax = b for any shape of A, modelled on
+    /// numpy.linalg.lstsq. Pass `None` for the default singular value cutoff.
+    pub fn lstsq(&self, b: &Vector, rcond: Option<f64>) -> Result<LeastSquares, LinAlgError> {
+        svd::lstsq(&self.data, &b.data, rcond)
+    }
+
+    pub fn eigenvector(&self, max_iters: usize, tolerance: f64) -> Result<Vector, LinAlgError> {
         let mut b_k = Vector {
             data: Array1::from_vec(vec![1.0; self.data.nrows()]),
         };
     
+        let mut residual = f64::INFINITY;
         for _ in 0..max_iters {
             let mut b_k1 = self.multiply_vector(&b_k)?;
             let norm = b_k1.magnitude();
             
-            b_k1.data.par_iter_mut().for_each(|val| *val /= norm);
+            parallel::run(b_k1.data.len(), |min_len| {
+                b_k1.data
+                    .par_iter_mut()
+                    .with_min_len(min_len)
+                    .for_each(|val| *val /= norm)
+            });
     
-            if (&b_k1.data - &b_k.data).par_iter().all(|&x| x.abs() < tolerance) {
+            residual = parallel::run(b_k1.data.len(), |min_len| {
+                (&b_k1.data - &b_k.data)
+                    .par_iter()
+                    .with_min_len(min_len)
+                    .map(|&x| x.abs())
+                    .reduce(|| 0.0, f64::max)
+            });
+            if residual < tolerance {
                 return Ok(b_k1);
             }
             b_k = b_k1;
         }
     
-        Err("Power iteration did not converge".to_string())
+        Err(LinAlgError::NoConvergence {
+            iterations: max_iters,
+            residual,
+        })
     }
 
-    pub fn eigenvalue(&self, v: &Vector) -> Result<f64, String> {
+    pub fn eigenvalue(&self, v: &Vector) -> Result<f64, LinAlgError> {
         let numerator = self.multiply_vector(v)?.dot(v)?;
         let denominator = v.dot(v)?;
 
         if denominator == 0.0 {
-            return Err("Denominator in Rayleigh quotient is zero".to_string());
+            return Err(LinAlgError::ZeroVector);
         }
 
         Ok(numerator / denominator)
     }
 
-    fn multiply_vector(&self, v: &Vector) -> Result<Vector, String> {
+    /// All eigenvalues of a symmetric matrix in increasing order, and a matrix
+    /// whose orthonormal columns are the corresponding eigenvectors.
+    pub fn symmetric_eigen(&self) -> Result<(Vector, Matrix), LinAlgError> {
+        self.hermitian_eigen()
+    }
+
+    /// Eigenvalues and, if requested, right eigenvectors of a general square
+    /// matrix. Complex conjugate pairs are returned next to each other.
+    pub fn eigen(&self, compute_vectors: bool) -> Result<EigenDecomposition, LinAlgError> {
+        eigen::eigen(&self.data, compute_vectors)
+    }
+
+    pub fn eigenvalues(&self) -> Result<Array1<Complex64>, LinAlgError> {
+        Ok(self.eigen(false)?.eigenvalues)
+    }
+
+    fn well_conditioned_lu(&self, min_rcond: f64) -> Result<LuFactor, LinAlgError> {
+        if !factorization::all_finite(&self.data) {
+            return Err(LinAlgError::NonFinite);
+        }
+        let lu = match self.lu_factor() {
+            Err(LinAlgError::Singular { .. }) => {
+                return Err(LinAlgError::IllConditioned { rcond: 0.0 })
+            }
+            result => result?,
+        };
+        let rcond = lu.rcond();
+        // A NaN estimate fails every comparison, so it is refused explicitly.
+        if rcond.is_nan() || rcond < min_rcond {
+            return Err(LinAlgError::IllConditioned { rcond });
+        }
+        Ok(lu)
+    }
+
+    fn multiply_vector(&self, v: &Vector) -> Result<Vector, LinAlgError> {
         if self.data.ncols() != v.data.len() {
-            return Err("Matrix and vector dimensions must match".to_string());
+            return Err(LinAlgError::DimensionMismatch {
+                expected: self.data.ncols(),
+                found: v.data.len(),
+            });
         }
     
-        let result_data: Vec<f64> = self.data.axis_iter(ndarray::Axis(0))
-            .into_par_iter()
-            .map(|row| row.iter().zip(v.data.iter()).map(|(&a, &b)| a * b).sum())
-            .collect();
+        let result_data: Vec<f64> = parallel::run(self.data.len(), |min_len| {
+            self.data
+                .axis_iter(ndarray::Axis(0))
+                .into_par_iter()
+                .with_min_len(min_len)
+                .map(|row| row.iter().zip(v.data.iter()).map(|(&a, &b)| a * b).sum())
+                .collect()
+        });
     
         Ok(Vector {
             data: Array1::from(result_data),
         })
     }
+}
 
-    pub fn kronecker_product(&self, other: &Matrix) -> Matrix {
-        let (a_rows, a_cols) = self.data.dim();
-        let (b_rows, b_cols) = other.data.dim();
+/// Result of `Matrix::plu_decomposition`, satisfying PA = LU. Row `i` of PA is
+/// row `permutation[i]` of A.
+pub struct PluDecomposition<T: Scalar = f64> {
+    pub permutation: Vec<usize>,
+    pub p: Matrix<T>,
+    pub l: Matrix<T>,
+    pub u: Matrix<T>,
+    pub swaps: usize,
+}
 
-        let mut result = Array2::<f64>::zeros((a_rows * b_rows, a_cols * b_cols));
+/// Matrix norms accepted by `Matrix::norm`. `One` and `Infinity` are the
+/// induced norms (maximum absolute column and row sums), `Spectral` is the
+/// largest singular value and `Nuclear` the sum of all singular values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormKind {
+    Frobenius,
+    One,
+    Infinity,
+    Spectral,
+    Nuclear,
+    MaxAbs,
+}
 
-        for a_row in 0..a_rows {
-            for a_col in 0..a_cols {
-                for b_row in 0..b_rows {
-                    for b_col in 0..b_cols {
-                        result[[a_row * b_rows + b_row, a_col * b_cols + b_col]] = 
-                            self.data[[a_row, a_col]] * other.data[[b_row, b_col]];
-                    }
-                }
-            }
+/// Metric used by `Vector::distance`. `Cosine` is 1 - cos(angle) and is not
+/// defined for zero vectors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceKind {
+    Euclidean,
+    Manhattan,
+    Chebyshev,
+    Cosine,
+}
+
+#[derive(Clone)]
+pub struct Vector<T: Scalar = f64> {
+    pub data: Array1<T>,
+}
+impl<T: Scalar> Vector<T> {
+    pub fn from_slice(values: &[T]) -> Vector<T> {
+        Vector {
+            data: Array1::from(values.to_vec()),
         }
+    }
 
-        Matrix { data: result }
+    pub fn add(&self, other: &Vector<T>) -> Result<Vector<T>, LinAlgError> {
+        self.zip_with(other, |a, b| a + b)
     }
 
+    pub fn sub(&self, other: &Vector<T>) -> Result<Vector<T>, LinAlgError> {
+        self.zip_with(other, |a, b| a - b)
+    }
 
-}
+    /// Element-wise product.
+    pub fn hadamard(&self, other: &Vector<T>) -> Result<Vector<T>, LinAlgError> {
+        self.zip_with(other, |a, b| a * b)
+    }
 
-#[derive(Clone)]
-pub struct Vector {
-    pub data: Array1<f64>,
-}
-impl Vector {
-    pub fn add(&self, other: &Vector) -> Vector {
-        let self_slice = self.data.view();
-        let other_slice = other.data.view();
-
-        let sum_data = self_slice
-            .as_slice()
-            .unwrap()
-            .par_iter()
-            .zip(other_slice.as_slice().unwrap().par_iter())
-            .map(|(&a, &b)| a + b)
-            .collect::<Vec<f64>>();
+    /// Element-wise quotient. Division by zero follows the scalar type's rules.
+    pub fn div(&self, other: &Vector<T>) -> Result<Vector<T>, LinAlgError> {
+        self.zip_with(other, |a, b| a / b)
+    }
 
+    pub fn scale(&self, alpha: T) -> Vector<T> {
         Vector {
-            data: Array1::from(sum_data),
+            data: self.data.mapv(|x| alpha * x),
         }
     }
-    pub fn dot(&self, other: &Vector) -> Result<f64, String> {
-        if self.data.len() != other.data.len() {
-            return Err("Vectors must be of the same length".to_string());
-        }
 
-        let self_slice = self.data.as_slice().unwrap();
-        let other_slice = other.data.as_slice().unwrap();
+    /// In-place self += alpha * x, as in BLAS axpy.
+    pub fn axpy(&mut self, alpha: T, x: &Vector<T>) -> Result<(), LinAlgError> {
+        self.check_length(x)?;
+        parallel::run(self.data.len(), |min_len| {
+            Zip::from(&mut self.data)
+                .and(&x.data)
+                .into_par_iter()
+                .with_min_len(min_len)
+                .for_each(|(y, &x)| *y += alpha * x)
+        });
+        Ok(())
+    }
+
+    pub fn dot(&self, other: &Vector<T>) -> Result<T, LinAlgError> {
+        self.check_length(other)?;
 
-        let dot_product = self_slice
-            .par_iter()
-            .zip(other_slice.par_iter())
-            .map(|(&a, &b)| a * b)
-            .sum();
+        let dot_product = parallel::run(self.data.len(), |min_len| {
+            Zip::from(&self.data)
+                .and(&other.data)
+                .into_par_iter()
+                .with_min_len(min_len)
+                .map(|(&a, &b)| a * b)
+                .sum()
+        });
 
         Ok(dot_product)
     }
 
-    pub fn magnitude(&self) -> f64 {
-        self.data.iter().map(|&x| x * x).sum::<f64>().sqrt()
+    /// Inner product that conjugates `self`, sum(conj(a_i) b_i), as in
+    /// numpy.vdot. Same as `dot` for real vectors.
+    pub fn vdot(&self, other: &Vector<T>) -> Result<T, LinAlgError> {
+        self.check_length(other)?;
+
+        Ok(self
+            .data
+            .iter()
+            .zip(other.data.iter())
+            .map(|(&a, &b)| a.conj() * b)
+            .sum())
+    }
+
+    pub fn magnitude(&self) -> T::Real {
+        self.data
+            .iter()
+            .map(|x| x.modulus_squared())
+            .sum::<T::Real>()
+            .sqrt()
     }
 
-    pub fn normalize(&self) -> Vector {
+    pub fn normalize(&self) -> Vector<T> {
         let mag = self.magnitude();
-        if mag == 0.0 {
+        if mag == T::Real::zero() {
             return self.clone();
         }
 
-        let normalized_data: Vec<f64> = self.data.iter().map(|&x| x / mag).collect();
+        let normalized_data: Vec<T> = self.data.iter().map(|&x| x / T::from_real(mag)).collect();
 
         Vector {
             data: Array1::from(normalized_data),
         }
     }
 
-    pub fn l1_norm(&self) -> f64 {
-        self.data.par_iter().map(|&x| x.abs()).sum()
+    /// Cross product of two 3-vectors.
+    pub fn cross(&self, other: &Vector<T>) -> Result<Vector<T>, LinAlgError> {
+        for v in [self, other].iter() {
+            if v.data.len() != 3 {
+                return Err(LinAlgError::DimensionMismatch {
+                    expected: 3,
+                    found: v.data.len(),
+                });
+            }
+        }
+
+        let (a, b) = (&self.data, &other.data);
+        Ok(Vector::from_slice(&[
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ]))
+    }
+
+    /// Outer product self other^T, without conjugation as in numpy.outer.
+    pub fn outer(&self, other: &Vector<T>) -> Matrix<T> {
+        Matrix::from_fn(self.data.len(), other.data.len(), |i, j| {
+            self.data[i] * other.data[j]
+        })
+    }
+
+    /// Angle in radians, in [0, pi]. For complex vectors this is the real
+    /// angle acos(Re<a, b> / (|a| |b|)).
+    pub fn angle_between(&self, other: &Vector<T>) -> Result<T::Real, LinAlgError> {
+        Ok(self.cosine_similarity(other)?.acos())
+    }
+
+    /// Component of `self` along `other`: (<other, self> / <other, other>) other.
+    pub fn project_onto(&self, other: &Vector<T>) -> Result<Vector<T>, LinAlgError> {
+        let denominator = other.vdot(other)?;
+        if denominator == T::zero() {
+            return Err(LinAlgError::ZeroVector);
+        }
+        Ok(other.scale(other.vdot(self)? / denominator))
+    }
+
+    /// Component of `self` orthogonal to `other`.
+    pub fn reject_from(&self, other: &Vector<T>) -> Result<Vector<T>, LinAlgError> {
+        self.sub(&self.project_onto(other)?)
+    }
+
+    pub fn distance(&self, other: &Vector<T>, kind: DistanceKind) -> Result<T::Real, LinAlgError> {
+        Ok(match kind {
+            DistanceKind::Euclidean => self.sub(other)?.l2_norm(),
+            DistanceKind::Manhattan => self.sub(other)?.l1_norm(),
+            DistanceKind::Chebyshev => self.sub(other)?.norm_p(T::Real::infinity()),
+            DistanceKind::Cosine => T::Real::one() - self.cosine_similarity(other)?,
+        })
+    }
+
+    pub fn l1_norm(&self) -> T::Real {
+        parallel::run(self.data.len(), |min_len| {
+            self.data
+                .par_iter()
+                .with_min_len(min_len)
+                .map(|x| x.modulus())
+                .sum()
+        })
+    }
+
+    pub fn l2_norm(&self) -> T::Real {
+        parallel::run(self.data.len(), |min_len| {
+            self.data
+                .par_iter()
+                .with_min_len(min_len)
+                .map(|x| x.modulus_squared())
+                .sum::<T::Real>()
+        })
+        .sqrt()
+    }
+
+    /// Vector p-norm (sum |x_i|^p)^(1/p) as in numpy.linalg.norm: p = inf and
+    /// p = -inf give the largest and smallest |x_i|, and p = 0 counts the
+    /// nonzero entries. Negative p is allowed and yields zero if any entry is.
+    pub fn norm_p(&self, p: T::Real) -> T::Real {
+        let zero = T::Real::zero();
+        let moduli = self.data.iter().map(|x| x.modulus());
+        if p == T::Real::infinity() {
+            moduli.fold(zero, nan_max)
+        } else if p == T::Real::neg_infinity() {
+            moduli.fold(T::Real::infinity(), nan_min)
+        } else if p == zero {
+            moduli
+                .filter(|&x| x != zero)
+                .fold(zero, |count, _| count + T::Real::one())
+        } else if p == T::Real::one() {
+            self.l1_norm()
+        } else if p > zero {
+            // Scaling by the largest entry avoids overflow and underflow in
+            // |x_i|^p. An infinite entry would turn into inf / inf = NaN.
+            let scale = self.norm_p(T::Real::infinity());
+            if scale == zero || scale.is_infinite() {
+                return scale;
+            }
+            let sum: T::Real = moduli.map(|x| (x / scale).powf(p)).sum();
+            scale * sum.powf(p.recip())
+        } else {
+            moduli.map(|x| x.powf(p)).sum::<T::Real>().powf(p.recip())
+        }
+    }
+
+    // Re<a, b> / (|a| |b|), clamped to [-1, 1] against rounding.
+    fn cosine_similarity(&self, other: &Vector<T>) -> Result<T::Real, LinAlgError> {
+        let norms = self.l2_norm() * other.l2_norm();
+        if norms == T::Real::zero() {
+            return Err(LinAlgError::ZeroVector);
+        }
+        let cosine = self.vdot(other)?.real() / norms;
+        Ok(cosine.max(-T::Real::one()).min(T::Real::one()))
+    }
+
+    fn check_length(&self, other: &Vector<T>) -> Result<(), LinAlgError> {
+        if self.data.len() != other.data.len() {
+            return Err(LinAlgError::DimensionMismatch {
+                expected: self.data.len(),
+                found: other.data.len(),
+            });
+        }
+        Ok(())
     }
 
-    pub fn l2_norm(&self) -> f64 {
-        self.data.par_iter().map(|&x| x * x).sum::<f64>().sqrt()
+    // Applies `f` element-wise, in parallel for long vectors. Works for any
+    // memory layout, unlike slice-based iteration.
+    fn zip_with<F>(&self, other: &Vector<T>, f: F) -> Result<Vector<T>, LinAlgError>
+    where
+        F: Fn(T, T) -> T + Sync + Send,
+    {
+        self.check_length(other)?;
+
+        let mut data = Array1::<T>::zeros(self.data.len());
+        parallel::run(data.len(), |min_len| {
+            Zip::from(&mut data)
+                .and(&self.data)
+                .and(&other.data)
+                .into_par_iter()
+                .with_min_len(min_len)
+                .for_each(|(out, &a, &b)| *out = f(a, b))
+        });
+
+        Ok(Vector { data })
     }
 }
@@ -0,0 +1,24 @@
+/// `matrix![1.0, 2.0; 3.0, 4.0]` builds a `Matrix` row by row. The rows are
+/// collected into a nested array, so rows of different lengths are rejected at
+/// compile time.
+#[macro_export]
+macro_rules! matrix {
+    () => {
+        $crate::Matrix::zero(0, 0)
+    };
+    ($($($x:expr),+ $(,)?);+ $(;)?) => {
+        $crate::Matrix::from_rows(&[$([$($x),+]),+])
+            .expect("matrix! rows must all have the same length")
+    };
+}
+
+/// `vector![1.0, 2.0, 3.0]` builds a `Vector` from its entries.
+#[macro_export]
+macro_rules! vector {
+    () => {
+        $crate::Vector::from_slice(&[])
+    };
+    ($($x:expr),+ $(,)?) => {
+        $crate::Vector::from_slice(&[$($x),+])
+    };
+}
@@ -1,66 +1,226 @@
-use linalg::{Matrix, Vector};
-use ndarray::{Array1, Array2};
+use linalg::{
+    matrix, set_parallel_config, vector, Complex64, DistanceKind, Matrix, NormKind, ParallelConfig,
+    Vector,
+};
 
 
 fn main() {
 
     // Matrix Examples
-    let a = Matrix {
-        data: Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 3.0, 4.0]).unwrap(),
-    };
-    let b = Matrix {
-        data: Array2::from_shape_vec((2, 2), vec![4.0, 3.0, 2.0, 1.0]).unwrap(),
-    };
+    let a = matrix![1.0, 2.0; 3.0, 4.0];
+    let b = matrix![4.0, 3.0; 2.0, 1.0];
 
     // Matrix Addition
     if let Ok(sum) = a.add(&b) {
-        println!("Matrix Addition:\n{:?}", sum.data);
+        println!("Matrix Addition:\n{:.4}", sum);
     }
 
     // Matrix Multiplication
     if let Ok(product) = a.multiply(&b) {
-        println!("Matrix Multiplication:\n{:?}", product.data);
+        println!("Matrix Multiplication:\n{:.4}", product);
+    }
+
+    // Strassen Multiplication (worthwhile for large matrices; the cutoff
+    // falls back to the regular kernel)
+    if let Ok(product) = a.multiply_strassen(&b, 256) {
+        println!("Strassen Multiplication:\n{:.4}", product);
     }
 
     // Matrix Transpose
     let transpose = a.transpose();
-    println!("Matrix Transpose:\n{:?}", transpose.data);
+    println!("Matrix Transpose:\n{:.4}", transpose);
 
     // Matrix Determinant
-    if let Some(determinant) = a.determinant() {
+    if let Ok(determinant) = a.determinant() {
         println!("Matrix Determinant: {:?}", determinant);
     }
 
+    // Matrix Determinant by cofactor expansion (small matrices only)
+    if let Ok(determinant) = a.determinant_cofactor() {
+        println!("Matrix Determinant (cofactor): {:?}", determinant);
+    }
+
     // Matrix Inverse
     if let Ok(inverse) = a.inverse() {
-        println!("Matrix Inverse:\n{:?}", inverse.data);
+        println!("Matrix Inverse:\n{:.4}", inverse);
+    }
+
+    // Solving Linear Systems
+    let rhs = vector![5.0, 6.0];
+    if let Ok(x) = a.solve(&rhs) {
+        println!("Solution of Ax = b: {:.4}", x);
+    }
+    if let Ok(x) = a.solve_matrix(&b) {
+        println!("Solution of AX = B:\n{:.4}", x);
+    }
+
+    // Reusable Factorizations
+    if let Ok(lu) = a.lu_factor() {
+        if let Ok(x) = lu.solve(&rhs) {
+            println!("LU Factor Solve: {:.4}", x);
+        }
+        if let Ok(x) = lu.solve_transpose(&rhs) {
+            println!("LU Factor Transpose Solve: {:.4}", x);
+        }
+        println!("LU Factor Determinant: {:?}", lu.determinant());
+        println!("LU Factor Reciprocal Condition: {:?}", lu.rcond());
+    }
+    let spd = matrix![4.0, 2.0; 2.0, 3.0];
+    if let Ok(x) = spd
+        .cholesky_factor()
+        .and_then(|cholesky| cholesky.solve(&rhs))
+    {
+        println!("Cholesky Factor Solve: {:.4}", x);
     }
+    if let Ok(x) = a.qr_factor().solve(&rhs) {
+        println!("QR Factor Solve: {:.4}", x);
+    }
+
+    // Matrix Cholesky Decomposition (symmetric positive definite)
+    if let Ok(l) = spd.cholesky() {
+        println!("Matrix Cholesky Decomposition - L:\n{:.4}", l);
+    }
+
+    // Matrix LDL^T Decomposition (symmetric indefinite)
+    let indefinite = matrix![0.0, 1.0; 1.0, 0.0];
+    if let Ok(ldlt) = indefinite.ldlt() {
+        println!("Matrix LDL^T Decomposition - L:\n{:.4}", ldlt.l());
+        println!("Matrix LDL^T Decomposition - D:\n{:.4}", ldlt.d());
+        println!(
+            "Matrix LDL^T Decomposition - Permutation: {:?}",
+            ldlt.permutation()
+        );
+    }
+
+    // Matrix QR Decomposition
+    let (q, r) = a.qr();
+    println!("Matrix QR Decomposition - Q:\n{:.4}", q);
+    println!("Matrix QR Decomposition - R:\n{:.4}", r);
+    let (q, r, permutation) = a.qr_pivoted();
+    println!("Matrix Pivoted QR Decomposition - Q:\n{:.4}", q);
+    println!("Matrix Pivoted QR Decomposition - R:\n{:.4}", r);
+    println!(
+        "Matrix Pivoted QR Decomposition - Permutation: {:?}",
+        permutation
+    );
 
     // Matrix LU Decomposition
     if let Ok((l, u)) = a.lu_decomposition() {
-        println!("Matrix LU Decomposition - L:\n{:?}", l.data);
-        println!("Matrix LU Decomposition - U:\n{:?}", u.data);
+        println!("Matrix LU Decomposition - L:\n{:.4}", l);
+        println!("Matrix LU Decomposition - U:\n{:.4}", u);
+    }
+
+    // Matrix LU Decomposition with partial pivoting (PA = LU)
+    if let Ok(plu) = a.plu_decomposition() {
+        println!("Matrix PLU Decomposition - P:\n{:.4}", plu.p);
+        println!("Matrix PLU Decomposition - L:\n{:.4}", plu.l);
+        println!("Matrix PLU Decomposition - U:\n{:.4}", plu.u);
+        println!("Matrix PLU Decomposition - Row swaps: {:?}", plu.swaps);
+    }
+
+    // Matrix Singular Value Decomposition
+    if let Ok(svd) = a.svd(true, true) {
+        if let (Some(u), Some(vt)) = (svd.u, svd.vt) {
+            println!("Matrix SVD - U:\n{:.4}", u);
+            println!("Matrix SVD - Singular Values: {:.4}", svd.singular_values);
+            println!("Matrix SVD - V^T:\n{:.4}", vt);
+        }
+    }
+
+    // Least-Squares Solution
+    let design = matrix![1.0, 0.0; 1.0, 1.0; 1.0, 2.0];
+    let observations = vector![1.0, 2.0, 2.0];
+    if let Ok(result) = design.lstsq(&observations, None) {
+        println!("Least-Squares Solution: {:.4}", result.solution);
+        println!("Least-Squares Residuals: {:?}", result.residuals);
+        println!("Least-Squares Rank: {:?}", result.rank);
+    }
+
+    // Moore-Penrose Pseudoinverse
+    if let Ok(pinv) = design.pinv(None) {
+        println!("Matrix Pseudoinverse:\n{:.4}", pinv);
     }
 
     // Matrix Norms
     println!("Matrix L1 Norm: {:?}", a.l1_norm());
     println!("Matrix L2 Norm: {:?}", a.l2_norm());
     println!("Matrix Infinity Norm: {:?}", a.infinity_norm());
+    for kind in [
+        NormKind::Frobenius,
+        NormKind::One,
+        NormKind::Infinity,
+        NormKind::Spectral,
+        NormKind::Nuclear,
+        NormKind::MaxAbs,
+    ]
+    .iter()
+    {
+        if let Ok(norm) = a.norm(*kind) {
+            println!("Matrix {:?} Norm: {:?}", kind, norm);
+        }
+    }
+
+    // Condition Numbers
+    if let Ok(cond) = a.cond(NormKind::Spectral) {
+        println!("Matrix Condition Number (2-norm): {:?}", cond);
+    }
+    if let Ok(rcond) = a.rcond_estimate() {
+        println!("Matrix Reciprocal Condition Estimate (1-norm): {:?}", rcond);
+    }
+    let ill_conditioned = Matrix::from_fn(10, 10, |i, j| 1.0 / (i + j + 1) as f64);
+    match ill_conditioned.solve_checked(&Vector::from_slice(&[1.0; 10]), 1e-10) {
+        Ok(x) => println!("Checked Solve: {:.4}", x),
+        Err(e) => println!("Checked Solve refused: {}", e),
+    }
+
+    // Rank, Fundamental Subspaces and Reduced Row Echelon Form
+    let deficient = matrix![1.0, 2.0, 3.0; 2.0, 4.0, 6.0; 1.0, 0.0, 1.0];
+    if let Ok(rank) = deficient.rank(None) {
+        println!("Matrix Rank: {:?}", rank);
+    }
+    if let Ok(null_space) = deficient.null_space(None) {
+        println!("Null Space Basis:\n{:.4}", null_space);
+    }
+    if let Ok(column_space) = deficient.column_space(None) {
+        println!("Column Space Basis:\n{:.4}", column_space);
+    }
+    if let Ok(row_space) = deficient.row_space(None) {
+        println!("Row Space Basis:\n{:.4}", row_space);
+    }
+    if let Ok(left_null_space) = deficient.left_null_space(None) {
+        println!("Left Null Space Basis:\n{:.4}", left_null_space);
+    }
+    let (rref, pivots) = deficient.rref();
+    println!("Reduced Row Echelon Form:\n{}", rref);
+    println!("Pivot Columns: {:?}", pivots);
 
     // Matrix Trace
     println!("Matrix Trace: {:?}", a.trace());
 
     // Vector Examples
-    let v1 = Vector {
-        data: Array1::from_vec(vec![1.0, 2.0, 3.0]),
-    };
-    let v2 = Vector {
-        data: Array1::from_vec(vec![4.0, 5.0, 6.0]),
-    };
+    let v1 = vector![1.0, 2.0, 3.0];
+    let v2 = vector![4.0, 5.0, 6.0];
 
     // Vector Addition
-    let v_sum = v1.add(&v2);
-    println!("Vector Addition: {:?}", v_sum.data);
+    if let Ok(v_sum) = v1.add(&v2) {
+        println!("Vector Addition: {:.4}", v_sum);
+    }
+
+    // Element-wise Vector Operations
+    if let Ok(difference) = v2.sub(&v1) {
+        println!("Vector Subtraction: {:.4}", difference);
+    }
+    if let Ok(product) = v1.hadamard(&v2) {
+        println!("Vector Hadamard Product: {:.4}", product);
+    }
+    if let Ok(quotient) = v2.div(&v1) {
+        println!("Vector Element-wise Division: {:.4}", quotient);
+    }
+    println!("Vector Scaling: {:.4}", v1.scale(2.0));
+    let mut accumulated = v2.clone();
+    if accumulated.axpy(0.5, &v1).is_ok() {
+        println!("Vector AXPY (v2 + 0.5 v1): {:.4}", accumulated);
+    }
 
     // Vector Dot Product
     if let Ok(dot_product) = v1.dot(&v2) {
@@ -72,34 +232,122 @@ fn main() {
 
     // Vector Normalization
     let normalized = v1.normalize();
-    println!("Vector Normalization: {:?}", normalized.data);
+    println!("Vector Normalization: {:.4}", normalized);
 
     // Vector Norms
     println!("Vector L1 Norm: {:?}", v1.l1_norm());
     println!("Vector L2 Norm: {:?}", v1.l2_norm());
+    println!("Vector L3 Norm: {:?}", v1.norm_p(3.0));
+    println!("Vector Max Norm: {:?}", v1.norm_p(f64::INFINITY));
+
+    // Vector Geometry
+    if let Ok(cross) = v1.cross(&v2) {
+        println!("Vector Cross Product: {:.4}", cross);
+    }
+    println!("Vector Outer Product:\n{:.4}", v1.outer(&v2));
+    if let Ok(angle) = v1.angle_between(&v2) {
+        println!("Angle Between Vectors: {:?}", angle);
+    }
+    if let Ok(projection) = v1.project_onto(&v2) {
+        println!("Projection of v1 onto v2: {:.4}", projection);
+    }
+    if let Ok(rejection) = v1.reject_from(&v2) {
+        println!("Rejection of v1 from v2: {:.4}", rejection);
+    }
+    for kind in [
+        DistanceKind::Euclidean,
+        DistanceKind::Manhattan,
+        DistanceKind::Chebyshev,
+        DistanceKind::Cosine,
+    ]
+    .iter()
+    {
+        if let Ok(distance) = v1.distance(&v2, *kind) {
+            println!("{:?} Distance: {:?}", kind, distance);
+        }
+    }
 
     // Eigenvector and Eigenvalue (if applicable)
     // Adjust the matrix to be suitable for eigenvector calculation
-    let eigen_matrix = Matrix {
-        data: Array2::from_shape_vec((2, 2), vec![2.0, 1.0, 1.0, 2.0]).unwrap(),
-    };
+    let eigen_matrix = matrix![2.0, 1.0; 1.0, 2.0];
 
     if let Ok(eigenvector) = eigen_matrix.eigenvector(1000, 1e-10) {
-        println!("Eigenvector: {:?}", eigenvector.data);
+        println!("Eigenvector: {:.4}", eigenvector);
         if let Ok(eigenvalue) = eigen_matrix.eigenvalue(&eigenvector) {
             println!("Corresponding Eigenvalue: {:?}", eigenvalue);
         }
     }
 
+    // Full Symmetric Eigendecomposition
+    if let Ok((eigenvalues, eigenvectors)) = eigen_matrix.symmetric_eigen() {
+        println!("Symmetric Eigenvalues: {:.4}", eigenvalues);
+        println!("Symmetric Eigenvectors:\n{:.4}", eigenvectors);
+    }
+
+    // General (Nonsymmetric) Eigendecomposition
+    let rotation = matrix![0.0, -1.0; 1.0, 0.0];
+    if let Ok(eigen) = rotation.eigen(true) {
+        println!("Eigenvalues: {:?}", eigen.eigenvalues);
+        if let Some(eigenvectors) = eigen.eigenvectors {
+            println!("Eigenvectors:\n{:?}", eigenvectors);
+        }
+    }
+
+    // Single-Precision and Complex Matrices
+    let single = matrix![1.0f32, 2.0; 3.0, 4.0];
+    if let Ok(inverse) = single.inverse() {
+        println!("f32 Matrix Inverse:\n{:.4}", inverse);
+    }
+    let complex = matrix![Complex64::new(1.0, 0.0), Complex64::new(0.0, 1.0); Complex64::new(0.0, -1.0), Complex64::new(2.0, 0.0)];
+    if let Ok(determinant) = complex.determinant() {
+        println!("Complex Matrix Determinant: {:?}", determinant);
+    }
+
+    println!(
+        "Complex Conjugate Transpose:\n{}",
+        complex.conjugate_transpose()
+    );
+    if let Ok((eigenvalues, eigenvectors)) = complex.hermitian_eigen() {
+        println!("Hermitian Eigenvalues: {:.4}", eigenvalues);
+        println!("Hermitian Eigenvectors:\n{:.4}", eigenvectors);
+    }
+
     // Kronecker Product Example
-    let matrix1 = Matrix {
-        data: Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 3.0, 4.0]).unwrap(),
-    };
-    let matrix2 = Matrix {
-        data: Array2::from_shape_vec((2, 2), vec![0.0, 5.0, 6.0, 7.0]).unwrap(),
-    };
+    let matrix1 = matrix![1.0, 2.0; 3.0, 4.0];
+    let matrix2 = matrix![0.0, 5.0; 6.0, 7.0];
 
     let kronecker = matrix1.kronecker_product(&matrix2);
-    println!("Kronecker Product:\n{:?}", kronecker.data);
+    println!("Kronecker Product:\n{:.4}", kronecker);
+
+    // Formatting and Export
+    println!("Matrix in Scientific Notation:\n{:.2e}", a);
+    println!("Matrix as LaTeX:\n{}", a.to_latex());
+    println!("Matrix as Markdown:\n{}", a.to_markdown());
+
+    // Matrix and Vector Constructors
+    let hilbert = Matrix::from_fn(3, 3, |i, j| 1.0 / (i + j + 1) as f64);
+    println!("Hilbert Matrix:\n{:.4}", hilbert);
+    if let Ok(columns) = Matrix::from_cols(&[[1.0, 2.0], [3.0, 4.0]]) {
+        println!("Matrix From Columns:\n{:.4}", columns);
+    }
+    let diagonal = Matrix::from_diag(&Vector::from_slice(&[1.0, 2.0, 3.0]));
+    println!("Diagonal Matrix:\n{:.4}", diagonal);
+    println!("Ones Matrix:\n{}", Matrix::<f64>::ones(2, 3));
+
+    // Parallelism Policy: run small operations serially and large ones on a
+    // dedicated two-thread pool
+    if let Ok(config) = ParallelConfig::new(4096).with_threads(2) {
+        set_parallel_config(config);
+    }
+    if let Ok(product) = hilbert.multiply(&hilbert) {
+        println!("Hilbert Matrix Squared:\n{:.4}", product);
+    }
+    set_parallel_config(ParallelConfig::default());
+
+    // Operator Overloading (panics on shape mismatch)
+    let combined = &(&matrix1 + &matrix2) * 2.0 - &matrix1 * &matrix2;
+    println!("Operator Expression:\n{:.4}", combined);
+    println!("Matrix Element (0, 1): {:?}", combined[(0, 1)]);
+    println!("Matrix-Vector Product: {}", &a * &rhs);
     
 }
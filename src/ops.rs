@@ -0,0 +1,353 @@
+//! Operator overloads for `Matrix` and `Vector`. These panic on shape mismatch;
+//! the `Result`-returning methods such as `Matrix::add` and `Matrix::multiply`
+//! remain for checked use. Every binary operator is implemented on references
+//! and forwarded for owned operands.
+use crate::{Complex32, Complex64, Matrix, Scalar, Vector};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+// Implements `$imp` for the owned/borrowed combinations of `$lhs` and `$rhs`
+// by delegating to the `&$lhs op &$rhs` implementation.
+macro_rules! forward_binop {
+    (impl $imp:ident, $method:ident for $lhs:ident, $rhs:ident -> $out:ident) => {
+        impl<T: Scalar> $imp<$rhs<T>> for $lhs<T> {
+            type Output = $out<T>;
+
+            fn $method(self, rhs: $rhs<T>) -> $out<T> {
+                $imp::$method(&self, &rhs)
+            }
+        }
+
+        impl<T: Scalar> $imp<&$rhs<T>> for $lhs<T> {
+            type Output = $out<T>;
+
+            fn $method(self, rhs: &$rhs<T>) -> $out<T> {
+                $imp::$method(&self, rhs)
+            }
+        }
+
+        impl<T: Scalar> $imp<$rhs<T>> for &$lhs<T> {
+            type Output = $out<T>;
+
+            fn $method(self, rhs: $rhs<T>) -> $out<T> {
+                $imp::$method(self, &rhs)
+            }
+        }
+    };
+}
+
+fn assert_same_shape<T: Scalar>(operation: &str, a: &Matrix<T>, b: &Matrix<T>) {
+    let ((m, n), (p, q)) = (a.data.dim(), b.data.dim());
+    if (m, n) != (p, q) {
+        panic!(
+            "Cannot {} a {}x{} matrix and a {}x{} matrix",
+            operation, m, n, p, q
+        );
+    }
+}
+
+fn assert_same_length<T: Scalar>(operation: &str, a: &Vector<T>, b: &Vector<T>) {
+    if a.data.len() != b.data.len() {
+        panic!(
+            "Cannot {} vectors of length {} and {}",
+            operation,
+            a.data.len(),
+            b.data.len()
+        );
+    }
+}
+
+impl<T: Scalar> Add<&Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn add(self, rhs: &Matrix<T>) -> Matrix<T> {
+        assert_same_shape("add", self, rhs);
+        Matrix {
+            data: &self.data + &rhs.data,
+        }
+    }
+}
+
+impl<T: Scalar> Sub<&Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn sub(self, rhs: &Matrix<T>) -> Matrix<T> {
+        assert_same_shape("subtract", self, rhs);
+        Matrix {
+            data: &self.data - &rhs.data,
+        }
+    }
+}
+
+impl<T: Scalar> Mul<&Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: &Matrix<T>) -> Matrix<T> {
+        let ((m, n), (p, q)) = (self.data.dim(), rhs.data.dim());
+        self.multiply(rhs).unwrap_or_else(|_| {
+            panic!(
+                "Cannot multiply a {}x{} matrix by a {}x{} matrix: inner dimensions {} and {} differ",
+                m, n, p, q, n, p
+            )
+        })
+    }
+}
+
+impl<T: Scalar> Mul<&Vector<T>> for &Matrix<T> {
+    type Output = Vector<T>;
+
+    fn mul(self, rhs: &Vector<T>) -> Vector<T> {
+        let (m, n) = self.data.dim();
+        if n != rhs.data.len() {
+            panic!(
+                "Cannot multiply a {}x{} matrix by a vector of length {}",
+                m,
+                n,
+                rhs.data.len()
+            );
+        }
+        Vector {
+            data: self.data.dot(&rhs.data),
+        }
+    }
+}
+
+impl<T: Scalar> Add<&Vector<T>> for &Vector<T> {
+    type Output = Vector<T>;
+
+    fn add(self, rhs: &Vector<T>) -> Vector<T> {
+        assert_same_length("add", self, rhs);
+        Vector {
+            data: &self.data + &rhs.data,
+        }
+    }
+}
+
+impl<T: Scalar> Sub<&Vector<T>> for &Vector<T> {
+    type Output = Vector<T>;
+
+    fn sub(self, rhs: &Vector<T>) -> Vector<T> {
+        assert_same_length("subtract", self, rhs);
+        Vector {
+            data: &self.data - &rhs.data,
+        }
+    }
+}
+
+forward_binop!(impl Add, add for Matrix, Matrix -> Matrix);
+forward_binop!(impl Sub, sub for Matrix, Matrix -> Matrix);
+forward_binop!(impl Mul, mul for Matrix, Matrix -> Matrix);
+forward_binop!(impl Mul, mul for Matrix, Vector -> Vector);
+forward_binop!(impl Add, add for Vector, Vector -> Vector);
+forward_binop!(impl Sub, sub for Vector, Vector -> Vector);
+
+// Scaling by a scalar on the right works for any `Scalar`; scaling on the
+// left has to be spelled out per type because of the orphan rule.
+impl<T: Scalar> Mul<T> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: T) -> Matrix<T> {
+        Matrix {
+            data: &self.data * rhs,
+        }
+    }
+}
+
+impl<T: Scalar> Mul<T> for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: T) -> Matrix<T> {
+        &self * rhs
+    }
+}
+
+impl<T: Scalar> Mul<T> for &Vector<T> {
+    type Output = Vector<T>;
+
+    fn mul(self, rhs: T) -> Vector<T> {
+        Vector {
+            data: &self.data * rhs,
+        }
+    }
+}
+
+impl<T: Scalar> Mul<T> for Vector<T> {
+    type Output = Vector<T>;
+
+    fn mul(self, rhs: T) -> Vector<T> {
+        &self * rhs
+    }
+}
+
+macro_rules! impl_left_scalar_mul {
+    ($($t:ty),*) => {
+        $(
+            impl Mul<Matrix<$t>> for $t {
+                type Output = Matrix<$t>;
+
+                fn mul(self, rhs: Matrix<$t>) -> Matrix<$t> {
+                    &rhs * self
+                }
+            }
+
+            impl Mul<&Matrix<$t>> for $t {
+                type Output = Matrix<$t>;
+
+                fn mul(self, rhs: &Matrix<$t>) -> Matrix<$t> {
+                    rhs * self
+                }
+            }
+
+            impl Mul<Vector<$t>> for $t {
+                type Output = Vector<$t>;
+
+                fn mul(self, rhs: Vector<$t>) -> Vector<$t> {
+                    &rhs * self
+                }
+            }
+
+            impl Mul<&Vector<$t>> for $t {
+                type Output = Vector<$t>;
+
+                fn mul(self, rhs: &Vector<$t>) -> Vector<$t> {
+                    rhs * self
+                }
+            }
+        )*
+    };
+}
+
+impl_left_scalar_mul!(f32, f64, Complex32, Complex64);
+
+impl<T: Scalar> Neg for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn neg(self) -> Matrix<T> {
+        Matrix {
+            data: self.data.mapv(|x| T::zero() - x),
+        }
+    }
+}
+
+impl<T: Scalar> Neg for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn neg(self) -> Matrix<T> {
+        -&self
+    }
+}
+
+impl<T: Scalar> Neg for &Vector<T> {
+    type Output = Vector<T>;
+
+    fn neg(self) -> Vector<T> {
+        Vector {
+            data: self.data.mapv(|x| T::zero() - x),
+        }
+    }
+}
+
+impl<T: Scalar> Neg for Vector<T> {
+    type Output = Vector<T>;
+
+    fn neg(self) -> Vector<T> {
+        -&self
+    }
+}
+
+impl<T: Scalar> AddAssign<&Matrix<T>> for Matrix<T> {
+    fn add_assign(&mut self, rhs: &Matrix<T>) {
+        assert_same_shape("add", self, rhs);
+        self.data += &rhs.data;
+    }
+}
+
+impl<T: Scalar> AddAssign<Matrix<T>> for Matrix<T> {
+    fn add_assign(&mut self, rhs: Matrix<T>) {
+        *self += &rhs;
+    }
+}
+
+impl<T: Scalar> SubAssign<&Matrix<T>> for Matrix<T> {
+    fn sub_assign(&mut self, rhs: &Matrix<T>) {
+        assert_same_shape("subtract", self, rhs);
+        self.data -= &rhs.data;
+    }
+}
+
+impl<T: Scalar> SubAssign<Matrix<T>> for Matrix<T> {
+    fn sub_assign(&mut self, rhs: Matrix<T>) {
+        *self -= &rhs;
+    }
+}
+
+impl<T: Scalar> AddAssign<&Vector<T>> for Vector<T> {
+    fn add_assign(&mut self, rhs: &Vector<T>) {
+        assert_same_length("add", self, rhs);
+        self.data += &rhs.data;
+    }
+}
+
+impl<T: Scalar> AddAssign<Vector<T>> for Vector<T> {
+    fn add_assign(&mut self, rhs: Vector<T>) {
+        *self += &rhs;
+    }
+}
+
+impl<T: Scalar> SubAssign<&Vector<T>> for Vector<T> {
+    fn sub_assign(&mut self, rhs: &Vector<T>) {
+        assert_same_length("subtract", self, rhs);
+        self.data -= &rhs.data;
+    }
+}
+
+impl<T: Scalar> SubAssign<Vector<T>> for Vector<T> {
+    fn sub_assign(&mut self, rhs: Vector<T>) {
+        *self -= &rhs;
+    }
+}
+
+impl<T: Scalar> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        let (m, n) = self.data.dim();
+        self.data.get((i, j)).unwrap_or_else(|| {
+            panic!(
+                "Index ({}, {}) is out of bounds for a {}x{} matrix",
+                i, j, m, n
+            )
+        })
+    }
+}
+
+impl<T: Scalar> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        let (m, n) = self.data.dim();
+        self.data.get_mut((i, j)).unwrap_or_else(|| {
+            panic!(
+                "Index ({}, {}) is out of bounds for a {}x{} matrix",
+                i, j, m, n
+            )
+        })
+    }
+}
+
+impl<T: Scalar> Index<usize> for Vector<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        let n = self.data.len();
+        self.data
+            .get(i)
+            .unwrap_or_else(|| panic!("Index {} is out of bounds for a vector of length {}", i, n))
+    }
+}
+
+impl<T: Scalar> IndexMut<usize> for Vector<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        let n = self.data.len();
+        self.data
+            .get_mut(i)
+            .unwrap_or_else(|| panic!("Index {} is out of bounds for a vector of length {}", i, n))
+    }
+}
@@ -0,0 +1,80 @@
+//! Crate-wide policy for the rayon-backed code paths. Operations whose work
+//! (vector length, or multiply-adds for matrix products and solves) is below
+//! `threshold` run serially on the calling thread; larger ones run on `pool`,
+//! or on rayon's global pool when no custom pool is set.
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+use std::sync::{Arc, RwLock};
+
+pub const DEFAULT_PARALLEL_THRESHOLD: usize = 1 << 14;
+
+static CONFIG: RwLock<Option<ParallelConfig>> = RwLock::new(None);
+
+/// When and where the crate's rayon-backed operations run in parallel.
+#[derive(Clone)]
+pub struct ParallelConfig {
+    pub threshold: usize,
+    pub pool: Option<Arc<ThreadPool>>,
+}
+
+impl ParallelConfig {
+    pub fn new(threshold: usize) -> ParallelConfig {
+        ParallelConfig {
+            threshold,
+            pool: None,
+        }
+    }
+
+    /// Never parallelise.
+    pub fn serial() -> ParallelConfig {
+        ParallelConfig::new(usize::MAX)
+    }
+
+    pub fn with_pool(mut self, pool: Arc<ThreadPool>) -> ParallelConfig {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Builds a dedicated pool with `num_threads` workers.
+    pub fn with_threads(self, num_threads: usize) -> Result<ParallelConfig, ThreadPoolBuildError> {
+        let pool = ThreadPoolBuilder::new().num_threads(num_threads).build()?;
+        Ok(self.with_pool(Arc::new(pool)))
+    }
+}
+
+impl Default for ParallelConfig {
+    fn default() -> ParallelConfig {
+        ParallelConfig::new(DEFAULT_PARALLEL_THRESHOLD)
+    }
+}
+
+/// Replaces the crate-wide configuration. Operations already running keep the
+/// configuration they started with.
+pub fn set_parallel_config(config: ParallelConfig) {
+    *CONFIG.write().unwrap_or_else(|e| e.into_inner()) = Some(config);
+}
+
+pub fn parallel_config() -> ParallelConfig {
+    CONFIG
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+// Runs `op` under the current policy. `op` receives the minimum number of items
+// per rayon task, to be passed to `with_min_len`: `usize::MAX` keeps the whole
+// iterator on the calling thread, 1 lets rayon split freely.
+pub(crate) fn run<R, F>(work: usize, op: F) -> R
+where
+    R: Send,
+    F: FnOnce(usize) -> R + Send,
+{
+    let config = parallel_config();
+    if work < config.threshold {
+        return op(usize::MAX);
+    }
+    match &config.pool {
+        Some(pool) => pool.install(|| op(1)),
+        None => op(1),
+    }
+}
@@ -0,0 +1,104 @@
+use ndarray::{LinalgScalar, ScalarOperand};
+use num_complex::{Complex32, Complex64};
+use num_traits::{Float, NumAssign};
+use std::fmt::Debug;
+use std::iter::{Product, Sum};
+
+/// Element type of `Matrix` and `Vector`. Implemented for f32, f64, Complex32
+/// and Complex64.
+pub trait Scalar:
+    LinalgScalar + NumAssign + ScalarOperand + Sum + Product + Debug + Send + Sync
+{
+    /// The real type underlying the scalar, used for magnitudes and norms.
+    type Real: Scalar<Real = Self::Real> + Float;
+
+    fn from_real(x: Self::Real) -> Self;
+
+    fn real(self) -> Self::Real;
+
+    /// Absolute value, or modulus for complex numbers.
+    fn modulus(self) -> Self::Real;
+
+    fn modulus_squared(self) -> Self::Real;
+
+    fn conj(self) -> Self;
+}
+
+macro_rules! impl_real_scalar {
+    ($t:ty) => {
+        impl Scalar for $t {
+            type Real = $t;
+
+            fn from_real(x: $t) -> $t {
+                x
+            }
+
+            fn real(self) -> $t {
+                self
+            }
+
+            fn modulus(self) -> $t {
+                self.abs()
+            }
+
+            fn modulus_squared(self) -> $t {
+                self * self
+            }
+
+            fn conj(self) -> $t {
+                self
+            }
+        }
+    };
+}
+
+macro_rules! impl_complex_scalar {
+    ($t:ty, $real:ty) => {
+        impl Scalar for $t {
+            type Real = $real;
+
+            fn from_real(x: $real) -> $t {
+                <$t>::new(x, 0.0)
+            }
+
+            fn real(self) -> $real {
+                self.re
+            }
+
+            fn modulus(self) -> $real {
+                self.norm()
+            }
+
+            fn modulus_squared(self) -> $real {
+                self.norm_sqr()
+            }
+
+            fn conj(self) -> $t {
+                <$t>::conj(&self)
+            }
+        }
+    };
+}
+
+impl_real_scalar!(f32);
+impl_real_scalar!(f64);
+impl_complex_scalar!(Complex32, f32);
+impl_complex_scalar!(Complex64, f64);
+
+// `Float::max` and `Float::min` return the other operand when one is NaN, so a
+// fold over them silently skips NaN entries. These propagate it instead.
+pub(crate) fn nan_max<R: Float>(x: R, y: R) -> R {
+    if x.is_nan() || y.is_nan() {
+        R::nan()
+    } else {
+        x.max(y)
+    }
+}
+
+pub(crate) fn nan_min<R: Float>(x: R, y: R) -> R {
+    if x.is_nan() || y.is_nan() {
+        R::nan()
+    } else {
+        x.min(y)
+    }
+}
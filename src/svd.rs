@@ -0,0 +1,318 @@
+use crate::factorization::QrFactor;
+use crate::{LinAlgError, Matrix, Vector};
+use ndarray::{Array1, Array2, Axis};
+
+const MAX_SWEEPS: usize = 60;
+
+/// Result of `Matrix::svd`, satisfying A = U diag(singular_values) V^T with the
+/// singular values in decreasing order. `u` and `vt` are `None` when they were
+/// not requested.
+pub struct Svd {
+    pub u: Option<Matrix>,
+    pub singular_values: Vector,
+    pub vt: Option<Matrix>,
+}
+
+/// Result of `Matrix::lstsq`. `residuals` is the squared 2-norm of b - Ax and is
+/// only reported when the system is overdetermined and has full column rank,
+/// matching numpy.linalg.lstsq.
+pub struct LeastSquares {
+    pub solution: Vector,
+    pub residuals: Option<f64>,
+    pub rank: usize,
+    pub singular_values: Vector,
+}
+
+// Thin SVD keeps min(m, n) singular vectors on each side; full SVD returns a
+// square m x m U and n x n V^T.
+pub(crate) fn svd(
+    a: &Array2<f64>,
+    compute_u: bool,
+    compute_v: bool,
+    full: bool,
+) -> Result<Svd, LinAlgError> {
+    // The Jacobi sweeps never settle on NaN or infinite entries.
+    if !a.iter().all(|x| x.is_finite()) {
+        return Err(LinAlgError::NonFinite);
+    }
+
+    let (m, n) = a.dim();
+    if m < n {
+        // A^T = U' S V'^T, so A = V' S U'^T.
+        let transposed = svd(&a.t().to_owned(), compute_v, compute_u, full)?;
+        return Ok(Svd {
+            u: transposed.vt.map(|vt| vt.transpose()),
+            singular_values: transposed.singular_values,
+            vt: transposed.u.map(|u| u.transpose()),
+        });
+    }
+
+    // For tall matrices the rotations are applied to the n x n triangular
+    // factor of A = QR instead of to A itself, and U is recovered as Q U_R.
+    let qr = if m > n { Some(QrFactor::new(a)) } else { None };
+    let work = match &qr {
+        Some(qr) => qr.r().data,
+        None => a.clone(),
+    };
+
+    let (columns, v) = one_sided_jacobi(work, compute_v)?;
+
+    let norms: Vec<f64> = columns
+        .axis_iter(Axis(1))
+        .map(|column| column.dot(&column).sqrt())
+        .collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| {
+        norms[j]
+            .partial_cmp(&norms[i])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let singular_values: Array1<f64> = order.iter().map(|&j| norms[j]).collect();
+
+    let u = if compute_u {
+        let cutoff = singular_values.get(0).copied().unwrap_or(0.0) * n as f64 * f64::EPSILON;
+        let mut u_work = Array2::<f64>::zeros((n, n));
+        let mut valid = vec![false; n];
+        for (k, &j) in order.iter().enumerate() {
+            if norms[j] > cutoff && norms[j] > 0.0 {
+                u_work
+                    .column_mut(k)
+                    .assign(&(&columns.column(j) / norms[j]));
+                valid[k] = true;
+            }
+        }
+        let u_work = complete_orthonormal(u_work, &valid);
+
+        let thin = match &qr {
+            Some(qr) => qr.q().data.dot(&u_work),
+            None => u_work,
+        };
+
+        if full && m > n {
+            let mut u_full = Array2::<f64>::zeros((m, m));
+            u_full.slice_mut(ndarray::s![.., ..n]).assign(&thin);
+            let valid: Vec<bool> = (0..m).map(|k| k < n).collect();
+            Some(Matrix {
+                data: complete_orthonormal(u_full, &valid),
+            })
+        } else {
+            Some(Matrix { data: thin })
+        }
+    } else {
+        None
+    };
+
+    let vt = v.map(|v| {
+        let mut vt = Array2::<f64>::zeros((n, n));
+        for (k, &j) in order.iter().enumerate() {
+            vt.row_mut(k).assign(&v.column(j));
+        }
+        Matrix { data: vt }
+    });
+
+    Ok(Svd {
+        u,
+        singular_values: Vector {
+            data: singular_values,
+        },
+        vt,
+    })
+}
+
+// Hestenes' one-sided Jacobi method. Rotates pairs of columns of `a` until
+// they are mutually orthogonal; the column norms are then the singular values.
+// Returns the rotated columns and, if requested, the accumulated rotations V.
+fn one_sided_jacobi(
+    mut a: Array2<f64>,
+    compute_v: bool,
+) -> Result<(Array2<f64>, Option<Array2<f64>>), LinAlgError> {
+    let n = a.ncols();
+    let mut v = if compute_v {
+        Some(Array2::<f64>::eye(n))
+    } else {
+        None
+    };
+
+    // Columns whose squared norm falls below this are rounding noise from a
+    // zero singular value and are left alone, otherwise the rotations never
+    // settle.
+    let negligible = (f64::EPSILON * a.iter().map(|x| x * x).sum::<f64>().sqrt()).powi(2);
+
+    // Largest cosine between two columns rotated in the last sweep.
+    let mut off_diagonal = 0.0f64;
+
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        off_diagonal = 0.0;
+
+        for p in 0..n {
+            for q in p + 1..n {
+                let alpha = a.column(p).dot(&a.column(p));
+                let beta = a.column(q).dot(&a.column(q));
+                let gamma = a.column(p).dot(&a.column(q));
+                if alpha.min(beta) <= negligible
+                    || gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt()
+                {
+                    continue;
+                }
+                rotated = true;
+                off_diagonal = off_diagonal.max(gamma.abs() / (alpha * beta).sqrt());
+
+                let zeta = (beta - alpha) / (2.0 * gamma);
+                let t = zeta.signum() / (zeta.abs() + 1.0f64.hypot(zeta));
+                let c = 1.0 / 1.0f64.hypot(t);
+                let s = c * t;
+
+                rotate_columns(&mut a, p, q, c, s);
+                if let Some(v) = v.as_mut() {
+                    rotate_columns(v, p, q, c, s);
+                }
+            }
+        }
+
+        if !rotated {
+            return Ok((a, v));
+        }
+    }
+
+    Err(LinAlgError::NoConvergence {
+        iterations: MAX_SWEEPS,
+        residual: off_diagonal,
+    })
+}
+
+fn rotate_columns(a: &mut Array2<f64>, p: usize, q: usize, c: f64, s: f64) {
+    for i in 0..a.nrows() {
+        let (x, y) = (a[[i, p]], a[[i, q]]);
+        a[[i, p]] = c * x - s * y;
+        a[[i, q]] = s * x + c * y;
+    }
+}
+
+// Replaces every column of `basis` not flagged in `valid` with a unit vector
+// orthogonal to all other columns. The flagged columns must already be
+// orthonormal.
+fn complete_orthonormal(mut basis: Array2<f64>, valid: &[bool]) -> Array2<f64> {
+    let m = basis.nrows();
+    let mut filled: Vec<usize> = (0..valid.len()).filter(|&k| valid[k]).collect();
+    let mut candidate = 0;
+
+    for k in (0..valid.len()).filter(|&k| !valid[k]) {
+        while candidate < m {
+            let mut x = Array1::<f64>::zeros(m);
+            x[candidate] = 1.0;
+            candidate += 1;
+
+            // Two passes of Gram-Schmidt keep the result orthogonal to
+            // working precision.
+            for _ in 0..2 {
+                for &j in &filled {
+                    let projection = basis.column(j).dot(&x);
+                    x.scaled_add(-projection, &basis.column(j));
+                }
+            }
+
+            let norm = x.dot(&x).sqrt();
+            if norm > 1e-6 {
+                basis.column_mut(k).assign(&(x / norm));
+                filled.push(k);
+                break;
+            }
+        }
+    }
+
+    basis
+}
+
+// Minimum-norm least-squares solution of Ax = b.
+pub(crate) fn lstsq(
+    a: &Array2<f64>,
+    b: &Array1<f64>,
+    rcond: Option<f64>,
+) -> Result<LeastSquares, LinAlgError> {
+    let (m, n) = a.dim();
+    if b.len() != m {
+        return Err(LinAlgError::DimensionMismatch {
+            expected: m,
+            found: b.len(),
+        });
+    }
+
+    let decomposition = svd(a, true, true, false)?;
+    let s = &decomposition.singular_values.data;
+    let u = decomposition.u.unwrap().data;
+    let vt = decomposition.vt.unwrap().data;
+
+    let tol = rcond.map(|rcond| rcond * s.get(0).copied().unwrap_or(0.0));
+    let rank = numerical_rank(s, tol, m, n);
+
+    // x = V_r S_r^-1 U_r^T b
+    let mut coefficients = u.t().dot(b);
+    for (k, c) in coefficients.iter_mut().enumerate() {
+        *c = if k < rank { *c / s[k] } else { 0.0 };
+    }
+    let solution = vt.t().dot(&coefficients);
+
+    let residuals = if rank == n && m > n {
+        let r = b - &a.dot(&solution);
+        Some(r.dot(&r))
+    } else {
+        None
+    };
+
+    Ok(LeastSquares {
+        solution: Vector { data: solution },
+        residuals,
+        rank,
+        singular_values: decomposition.singular_values,
+    })
+}
+
+// Moore-Penrose pseudoinverse A+ = V_r S_r^-1 U_r^T.
+pub(crate) fn pinv(a: &Array2<f64>, rcond: Option<f64>) -> Result<Array2<f64>, LinAlgError> {
+    let (m, n) = a.dim();
+    let decomposition = svd(a, true, true, false)?;
+    let s = &decomposition.singular_values.data;
+    let u = decomposition.u.unwrap().data;
+    let vt = decomposition.vt.unwrap().data;
+
+    let tol = rcond.map(|rcond| rcond * s.get(0).copied().unwrap_or(0.0));
+    let rank = numerical_rank(s, tol, m, n);
+    let mut scaled_vt = vt.slice(ndarray::s![..rank, ..]).to_owned();
+    for (k, mut row) in scaled_vt.outer_iter_mut().enumerate() {
+        row /= s[k];
+    }
+
+    Ok(scaled_vt.t().dot(&u.slice(ndarray::s![.., ..rank]).t()))
+}
+
+// Number of singular values above `tol`; those at or below it are treated as
+// zero. The default tolerance s_max * max(m, n) * eps matches
+// numpy.linalg.matrix_rank and, as a relative cutoff, numpy.linalg.lstsq.
+fn numerical_rank(s: &Array1<f64>, tol: Option<f64>, m: usize, n: usize) -> usize {
+    let tol =
+        tol.unwrap_or_else(|| s.get(0).copied().unwrap_or(0.0) * m.max(n) as f64 * f64::EPSILON);
+    s.iter().filter(|&&sigma| sigma > tol).count()
+}
+
+pub(crate) fn rank(a: &Array2<f64>, tol: Option<f64>) -> Result<usize, LinAlgError> {
+    let (m, n) = a.dim();
+    let s = svd(a, false, false, false)?.singular_values.data;
+    Ok(numerical_rank(&s, tol, m, n))
+}
+
+// Full SVD split at the numerical rank r: returns r, U (m x m) and V (n x n).
+// The first r columns of U and V span the column and row spaces, the rest the
+// left null space and null space.
+pub(crate) fn subspaces(
+    a: &Array2<f64>,
+    tol: Option<f64>,
+) -> Result<(usize, Array2<f64>, Array2<f64>), LinAlgError> {
+    let (m, n) = a.dim();
+    let decomposition = svd(a, true, true, true)?;
+    let rank = numerical_rank(&decomposition.singular_values.data, tol, m, n);
+    let u = decomposition.u.unwrap().data;
+    let v = decomposition.vt.unwrap().data.reversed_axes();
+    Ok((rank, u, v))
+}
@@ -1,10 +1,17 @@
+use linalg::DistanceKind;
+use linalg::LinAlgError;
 use linalg::Matrix;
+use linalg::NormKind;
 use linalg::Vector;
-use ndarray::{Array1, Array2};
+use linalg::{matrix, vector};
+use linalg::{parallel_config, set_parallel_config, ParallelConfig, DEFAULT_PARALLEL_THRESHOLD};
+use linalg::{Complex32, Complex64};
+use ndarray::{s, Array1, Array2, Axis};
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::f64::consts::SQRT_2;
+    use std::time::{Duration, Instant};
 
     fn assert_matrix_eq(a: &Array2<f64>, b: &Array2<f64>, tol: f64) {
         assert!(a.iter().zip(b.iter()).all(|(&x, &y)| (x - y).abs() < tol));
@@ -47,7 +54,7 @@ mod tests {
         let b = Vector {
             data: Array1::from_vec(vec![4.0, 5.0, 6.0]),
         };
-        let result = a.add(&b);
+        let result = a.add(&b).unwrap();
 
         let expected = Array1::from_vec(vec![5.0, 7.0, 9.0]);
         assert_eq!(result.data, expected);
@@ -144,7 +151,10 @@ mod tests {
         };
         let determinant = a.determinant();
 
-        assert_eq!(determinant, None);
+        assert_eq!(
+            determinant.err(),
+            Some(LinAlgError::NotSquare { rows: 2, cols: 3 })
+        );
     }
     #[test]
     fn test_matrix_inverse() {
@@ -158,7 +168,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Matrix is not invertible")]
+    #[should_panic(expected = "Singular")]
     fn test_matrix_inverse_non_invertible() {
         let a = Matrix {
             data: Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 2.0, 4.0]).unwrap(),
@@ -223,7 +233,7 @@ mod tests {
         let v = Vector {
             data: Array1::from_vec(vec![1.0, 2.0, 3.0, 4.0]),
         };
-        let norm = v.l2_norm();
+        let norm: f64 = v.l2_norm();
 
         let expected = 5.477225575051661; // sqrt(1^2 + 2^2 + 3^2 + 4^2)
         assert!((norm - expected).abs() < 1e-10);
@@ -256,6 +266,1651 @@ mod tests {
         assert!((eigenvalue - expected).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_plu_decomposition_zero_pivot() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![0.0, 1.0, 1.0, 0.0]).unwrap(),
+        };
+        let plu = a.plu_decomposition().unwrap();
+
+        assert_eq!(plu.permutation, vec![1, 0]);
+        assert_eq!(plu.swaps, 1);
+        assert_matrix_eq(&plu.l.data, &Array2::eye(2), 1e-12);
+        assert_matrix_eq(&plu.u.data, &Array2::eye(2), 1e-12);
+    }
+
+    #[test]
+    fn test_plu_decomposition_reconstructs() {
+        let a = Matrix {
+            data: Array2::from_shape_vec(
+                (3, 3),
+                vec![2.0, 1.0, 1.0, 4.0, -6.0, 0.0, -2.0, 7.0, 2.0],
+            )
+            .unwrap(),
+        };
+        let plu = a.plu_decomposition().unwrap();
+
+        let pa = plu.p.multiply(&a).unwrap();
+        let lu = plu.l.multiply(&plu.u).unwrap();
+        assert_matrix_eq(&pa.data, &lu.data, 1e-12);
+        assert!(plu.l.data.iter().all(|&x| x.abs() <= 1.0));
+    }
+
+    #[test]
+    fn test_plu_decomposition_singular() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 2.0, 4.0]).unwrap(),
+        };
+        assert!(a.plu_decomposition().is_err());
+    }
+
+    #[test]
+    fn test_matrix_determinant_matches_cofactor() {
+        let a = Matrix {
+            data: Array2::from_shape_vec(
+                (4, 4),
+                vec![
+                    3.0, 2.0, -1.0, 4.0, 2.0, 1.0, 5.0, 7.0, 0.0, 5.0, 2.0, -6.0, -1.0, 2.0, 1.0,
+                    0.0,
+                ],
+            )
+            .unwrap(),
+        };
+        let lu_det: f64 = a.determinant().unwrap();
+        let cofactor_det = a.determinant_cofactor().unwrap();
+
+        assert!((lu_det - cofactor_det).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_matrix_determinant_singular() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 2.0, 4.0]).unwrap(),
+        };
+        assert_eq!(a.determinant(), Ok(0.0));
+    }
+
+    #[test]
+    fn test_matrix_inverse_large() {
+        let n = 200;
+        let a = Matrix {
+            data: Array2::from_shape_fn((n, n), |(i, j)| {
+                if i == j {
+                    n as f64
+                } else {
+                    ((i * 7 + j * 13) % 11) as f64 - 5.0
+                }
+            }),
+        };
+        let inverse = a.inverse().unwrap();
+        let product = a.multiply(&inverse).unwrap();
+
+        assert_matrix_eq(&product.data, &Array2::eye(n), 1e-10);
+    }
+
+    #[test]
+    fn test_matrix_solve() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((3, 3), vec![0.0, 2.0, 1.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0])
+                .unwrap(),
+        };
+        let b = Vector {
+            data: Array1::from_vec(vec![5.0, 5.0, 12.0]),
+        };
+        let x = a.solve(&b).unwrap();
+
+        let expected = Array1::from_vec(vec![1.0, 1.0, 3.0]);
+        assert_vector_eq(&x.data, &expected, 1e-12);
+    }
+
+    #[test]
+    fn test_matrix_solve_matrix() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![4.0, 7.0, 2.0, 6.0]).unwrap(),
+        };
+        let b = Matrix {
+            data: Array2::from_shape_vec((2, 3), vec![1.0, 0.0, 11.0, 0.0, 1.0, 8.0]).unwrap(),
+        };
+        let x = a.solve_matrix(&b).unwrap();
+
+        let expected =
+            Array2::from_shape_vec((2, 3), vec![0.6, -0.7, 1.0, -0.2, 0.4, 1.0]).unwrap();
+        assert_matrix_eq(&x.data, &expected, 1e-12);
+    }
+
+    #[test]
+    fn test_matrix_solve_errors() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 2.0, 4.0]).unwrap(),
+        };
+        let b = Vector {
+            data: Array1::from_vec(vec![1.0, 2.0]),
+        };
+        assert_eq!(a.solve(&b).err(), Some(LinAlgError::Singular { pivot: 1 }));
+
+        let short = Vector {
+            data: Array1::from_vec(vec![1.0]),
+        };
+        assert!(a.solve(&short).is_err());
+    }
+
+    #[test]
+    fn test_lu_factor_reuse() {
+        let a = Matrix {
+            data: Array2::from_shape_vec(
+                (3, 3),
+                vec![2.0, 1.0, 1.0, 4.0, -6.0, 0.0, -2.0, 7.0, 2.0],
+            )
+            .unwrap(),
+        };
+        let lu = a.lu_factor().unwrap();
+
+        for rhs in [
+            vec![5.0, -2.0, 9.0],
+            vec![1.0, 0.0, 0.0],
+            vec![-3.0, 4.0, 0.5],
+        ] {
+            let b = Vector {
+                data: Array1::from_vec(rhs),
+            };
+            let x = lu.solve(&b).unwrap();
+            assert_vector_eq(&a.data.dot(&x.data), &b.data, 1e-12);
+
+            let y = lu.solve_transpose(&b).unwrap();
+            assert_vector_eq(&a.data.t().dot(&y.data), &b.data, 1e-12);
+        }
+
+        assert!((lu.determinant() - a.determinant_cofactor().unwrap()).abs() < 1e-12);
+        let product = a.multiply(&lu.inverse()).unwrap();
+        assert_matrix_eq(&product.data, &Array2::eye(3), 1e-12);
+    }
+
+    #[test]
+    fn test_lu_factor_rcond() {
+        let identity = Matrix::identity(4);
+        assert!((identity.lu_factor().unwrap().rcond() - 1.0).abs() < 1e-12);
+
+        let hilbert = Matrix {
+            data: Array2::from_shape_fn((8, 8), |(i, j)| 1.0 / (i + j + 1) as f64),
+        };
+        assert!(hilbert.lu_factor().unwrap().rcond() < 1e-9);
+    }
+
+    #[test]
+    fn test_cholesky_factor() {
+        let a = Matrix {
+            data: Array2::from_shape_vec(
+                (3, 3),
+                vec![4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0],
+            )
+            .unwrap(),
+        };
+        let chol = a.cholesky_factor().unwrap();
+
+        let expected_l =
+            Array2::from_shape_vec((3, 3), vec![2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0])
+                .unwrap();
+        assert_matrix_eq(&chol.l().data, &expected_l, 1e-12);
+        assert!((chol.determinant() - 36.0).abs() < 1e-9);
+
+        let b = Vector {
+            data: Array1::from_vec(vec![1.0, 2.0, 3.0]),
+        };
+        let x = chol.solve(&b).unwrap();
+        let expected = a.solve(&b).unwrap();
+        assert_vector_eq(&x.data, &expected.data, 1e-9);
+    }
+
+    #[test]
+    fn test_cholesky_factor_not_positive_definite() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 2.0, 1.0]).unwrap(),
+        };
+        let err = a.cholesky_factor().err().unwrap();
+        assert_eq!(err, LinAlgError::NotPositiveDefinite { pivot: 1 });
+    }
+
+    #[test]
+    fn test_qr_factor_solve() {
+        let a: Matrix = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![4.0, 7.0, 2.0, 6.0]).unwrap(),
+        };
+        let qr = a.qr_factor();
+        assert!((qr.determinant().unwrap() - 10.0).abs() < 1e-12);
+
+        let inverse = qr.inverse().unwrap();
+        let expected = Array2::from_shape_vec((2, 2), vec![0.6, -0.7, -0.2, 0.4]).unwrap();
+        assert_matrix_eq(&inverse.data, &expected, 1e-12);
+
+        // Least-squares line through (0, 1), (1, 3), (2, 4)
+        let design = Matrix {
+            data: Array2::from_shape_vec((3, 2), vec![1.0, 0.0, 1.0, 1.0, 1.0, 2.0]).unwrap(),
+        };
+        let b = Vector {
+            data: Array1::from_vec(vec![1.0, 3.0, 4.0]),
+        };
+        let x = design.qr_factor().solve(&b).unwrap();
+        let expected = Array1::from_vec(vec![7.0 / 6.0, 1.5]);
+        assert_vector_eq(&x.data, &expected, 1e-12);
+        assert!(design.qr_factor().determinant().is_err());
+    }
+
+    #[test]
+    fn test_qr_thin_and_full() {
+        let a = Matrix {
+            data: Array2::from_shape_vec(
+                (4, 3),
+                vec![
+                    12.0, -51.0, 4.0, 6.0, 167.0, -68.0, -4.0, 24.0, -41.0, 1.0, 2.0, 3.0,
+                ],
+            )
+            .unwrap(),
+        };
+
+        let (q, r) = a.qr();
+        assert_eq!(q.data.dim(), (4, 3));
+        assert_eq!(r.data.dim(), (3, 3));
+        assert_matrix_eq(&q.data.t().dot(&q.data), &Array2::eye(3), 1e-12);
+        assert_matrix_eq(&q.data.dot(&r.data), &a.data, 1e-10);
+        assert!((0..3).all(|i| (0..i).all(|j| r.data[[i, j]] == 0.0)));
+
+        let (q, r) = a.qr_full();
+        assert_eq!(q.data.dim(), (4, 4));
+        assert_eq!(r.data.dim(), (4, 3));
+        assert_matrix_eq(&q.data.t().dot(&q.data), &Array2::eye(4), 1e-12);
+        assert_matrix_eq(&q.data.dot(&r.data), &a.data, 1e-10);
+    }
+
+    #[test]
+    fn test_qr_wide() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap(),
+        };
+        let (q, r) = a.qr();
+
+        assert_eq!(q.data.dim(), (2, 2));
+        assert_eq!(r.data.dim(), (2, 3));
+        assert_matrix_eq(&q.data.dot(&r.data), &a.data, 1e-12);
+    }
+
+    #[test]
+    fn test_qr_pivoted_reveals_rank() {
+        // Third column is the sum of the first two
+        let a = Matrix {
+            data: Array2::from_shape_vec(
+                (4, 3),
+                vec![1.0, 0.0, 1.0, 2.0, 1.0, 3.0, 0.0, 1.0, 1.0, 1.0, 4.0, 5.0],
+            )
+            .unwrap(),
+        };
+        let (q, r, permutation) = a.qr_pivoted();
+
+        let mut permuted = Array2::<f64>::zeros((4, 3));
+        for (j, &column) in permutation.iter().enumerate() {
+            permuted.column_mut(j).assign(&a.data.column(column));
+        }
+        assert_matrix_eq(&q.data.dot(&r.data), &permuted, 1e-12);
+
+        let diag: Vec<f64> = r.data.diag().iter().map(|x| x.abs()).collect();
+        assert!(diag[0] >= diag[1] && diag[1] >= diag[2]);
+        assert!(diag[2] < 1e-12);
+    }
+
+    #[test]
+    fn test_qr_factor_pivoted_solve() {
+        let a: Matrix = Matrix {
+            data: Array2::from_shape_vec((3, 3), vec![1.0, 5.0, 2.0, 0.0, 3.0, 8.0, 4.0, 1.0, 1.0])
+                .unwrap(),
+        };
+        let qr = a.qr_factor_pivoted();
+        assert!((qr.determinant().unwrap() - a.determinant().unwrap()).abs() < 1e-10);
+
+        let b = Vector {
+            data: Array1::from_vec(vec![1.0, 2.0, 3.0]),
+        };
+        let x = qr.solve(&b).unwrap();
+        assert_vector_eq(&a.data.dot(&x.data), &b.data, 1e-12);
+        let y = qr.solve_transpose(&b).unwrap();
+        assert_vector_eq(&a.data.t().dot(&y.data), &b.data, 1e-12);
+    }
+
+    #[test]
+    fn test_cholesky() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![4.0, 2.0, 2.0, 3.0]).unwrap(),
+        };
+        let l = a.cholesky().unwrap();
+
+        assert_eq!(l.data[[0, 1]], 0.0);
+        assert_matrix_eq(&l.data.dot(&l.data.t()), &a.data, 1e-12);
+    }
+
+    #[test]
+    fn test_cholesky_names_failing_pivot() {
+        let a = Matrix {
+            data: Array2::from_shape_vec(
+                (3, 3),
+                vec![2.0, 1.0, 0.0, 1.0, 2.0, 0.0, 0.0, 0.0, -1.0],
+            )
+            .unwrap(),
+        };
+        let err = a.cholesky().err().unwrap();
+        assert_eq!(err, LinAlgError::NotPositiveDefinite { pivot: 2 });
+        assert!(err.to_string().contains("not positive definite"));
+
+        let non_symmetric = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![2.0, 1.0, 0.0, 2.0]).unwrap(),
+        };
+        assert_eq!(
+            non_symmetric.cholesky().err(),
+            Some(LinAlgError::NotSymmetric)
+        );
+    }
+
+    #[test]
+    fn test_ldlt_two_by_two_pivot() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![0.0, 1.0, 1.0, 0.0]).unwrap(),
+        };
+        let ldlt = a.ldlt().unwrap();
+
+        assert_eq!(ldlt.block_sizes(), &[2]);
+        assert!((ldlt.determinant() + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ldlt_indefinite() {
+        let a = Matrix {
+            data: Array2::from_shape_vec(
+                (4, 4),
+                vec![
+                    1.0, 10.0, 3.0, -2.0, 10.0, 0.0, 1.0, 4.0, 3.0, 1.0, -5.0, 0.5, -2.0, 4.0, 0.5,
+                    2.0,
+                ],
+            )
+            .unwrap(),
+        };
+        let ldlt = a.ldlt().unwrap();
+
+        let (p, l, d) = (ldlt.p().data, ldlt.l().data, ldlt.d().data);
+        let pap = p.dot(&a.data).dot(&p.t());
+        assert_matrix_eq(&pap, &l.dot(&d).dot(&l.t()), 1e-10);
+        assert!(l.iter().all(|x| x.abs() < 10.0));
+
+        assert!((ldlt.determinant() - a.determinant().unwrap()).abs() < 1e-8);
+
+        let b = Vector {
+            data: Array1::from_vec(vec![1.0, -1.0, 2.0, 0.0]),
+        };
+        let x = ldlt.solve(&b).unwrap();
+        assert_vector_eq(&a.data.dot(&x.data), &b.data, 1e-10);
+    }
+
+    #[test]
+    fn test_svd_known_values() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![3.0, 0.0, 4.0, 5.0]).unwrap(),
+        };
+        let svd = a.svd(true, true).unwrap();
+
+        let expected = Array1::from_vec(vec![3.0 * 5f64.sqrt(), 5f64.sqrt()]);
+        assert_vector_eq(&svd.singular_values.data, &expected, 1e-12);
+
+        let u = svd.u.unwrap().data;
+        let vt = svd.vt.unwrap().data;
+        let s = Array2::from_diag(&svd.singular_values.data);
+        assert_matrix_eq(&u.dot(&s).dot(&vt), &a.data, 1e-12);
+    }
+
+    #[test]
+    fn test_svd_thin_and_full_shapes() {
+        let a = Matrix {
+            data: Array2::from_shape_fn((5, 3), |(i, j)| ((i * 3 + j) as f64).sin()),
+        };
+
+        let thin = a.svd(true, true).unwrap();
+        let (u, vt) = (thin.u.unwrap().data, thin.vt.unwrap().data);
+        assert_eq!(u.dim(), (5, 3));
+        assert_eq!(vt.dim(), (3, 3));
+        assert_matrix_eq(&u.t().dot(&u), &Array2::eye(3), 1e-12);
+        let s = Array2::from_diag(&thin.singular_values.data);
+        assert_matrix_eq(&u.dot(&s).dot(&vt), &a.data, 1e-12);
+
+        let full = a.svd_full(true, true).unwrap();
+        let u = full.u.unwrap().data;
+        assert_eq!(u.dim(), (5, 5));
+        assert_matrix_eq(&u.t().dot(&u), &Array2::eye(5), 1e-12);
+
+        let wide = a.transpose().svd_full(true, true).unwrap();
+        assert_eq!(wide.u.unwrap().data.dim(), (3, 3));
+        assert_eq!(wide.vt.unwrap().data.dim(), (5, 5));
+        assert_vector_eq(
+            &wide.singular_values.data,
+            &thin.singular_values.data,
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn test_svd_rank_deficient() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((3, 3), vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 1.0, 0.0, 1.0])
+                .unwrap(),
+        };
+        let svd = a.svd(true, false).unwrap();
+        assert!(svd.vt.is_none());
+        assert!(svd.singular_values.data[2] < 1e-12);
+
+        let u = svd.u.unwrap().data;
+        assert_matrix_eq(&u.t().dot(&u), &Array2::eye(3), 1e-12);
+    }
+
+    #[test]
+    fn test_svd_rejects_non_finite() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![1.0, f64::NAN, 0.0, 1.0]).unwrap(),
+        };
+        assert_eq!(a.svd(true, true).err(), Some(LinAlgError::NonFinite));
+
+        let b = Matrix {
+            data: Array2::from_shape_vec((3, 2), vec![1.0, 0.0, f64::INFINITY, 1.0, 0.0, 2.0])
+                .unwrap(),
+        };
+        assert_eq!(b.singular_values().err(), Some(LinAlgError::NonFinite));
+        assert_eq!(b.pinv(None).err(), Some(LinAlgError::NonFinite));
+    }
+
+    #[test]
+    fn test_singular_values_only() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 3), vec![1.0, 0.0, 0.0, 0.0, 0.0, -2.0]).unwrap(),
+        };
+        let values = a.singular_values().unwrap();
+        assert_vector_eq(&values.data, &Array1::from_vec(vec![2.0, 1.0]), 1e-12);
+    }
+
+    #[test]
+    fn test_symmetric_eigen() {
+        let a = Matrix {
+            data: Array2::from_shape_vec(
+                (3, 3),
+                vec![2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0],
+            )
+            .unwrap(),
+        };
+        let (values, vectors) = a.symmetric_eigen().unwrap();
+
+        let expected = Array1::from_vec(vec![2.0 - SQRT_2, 2.0, 2.0 + SQRT_2]);
+        assert_vector_eq(&values.data, &expected, 1e-12);
+        assert_matrix_eq(&vectors.data.t().dot(&vectors.data), &Array2::eye(3), 1e-12);
+        let reconstructed = vectors
+            .data
+            .dot(&Array2::from_diag(&values.data))
+            .dot(&vectors.data.t());
+        assert_matrix_eq(&reconstructed, &a.data, 1e-12);
+    }
+
+    #[test]
+    fn test_symmetric_eigen_indefinite_spectrum() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![1.0, 0.0, 0.0, -1.0]).unwrap(),
+        };
+        let (values, vectors) = a.symmetric_eigen().unwrap();
+
+        assert_vector_eq(&values.data, &Array1::from_vec(vec![-1.0, 1.0]), 1e-12);
+        assert!((vectors.data[[1, 0]].abs() - 1.0).abs() < 1e-12);
+        assert!((vectors.data[[0, 1]].abs() - 1.0).abs() < 1e-12);
+
+        let non_symmetric = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 3.0, 4.0]).unwrap(),
+        };
+        assert!(non_symmetric.symmetric_eigen().is_err());
+    }
+
+    #[test]
+    fn test_eigen_complex_pair() {
+        let rotation = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![0.0, -1.0, 1.0, 0.0]).unwrap(),
+        };
+        let values = rotation.eigenvalues().unwrap();
+
+        assert!((values[0] - Complex64::new(0.0, 1.0)).norm() < 1e-12);
+        assert!((values[1] - Complex64::new(0.0, -1.0)).norm() < 1e-12);
+    }
+
+    #[test]
+    fn test_eigen_vectors() {
+        let a = Matrix {
+            data: Array2::from_shape_vec(
+                (4, 4),
+                vec![
+                    4.0, -2.0, 1.0, 3.0, 1.0, 0.0, -1.0, 2.0, 0.0, 5.0, 2.0, -3.0, 2.0, 1.0, 0.0,
+                    1.0,
+                ],
+            )
+            .unwrap(),
+        };
+        let eigen = a.eigen(true).unwrap();
+        let vectors = eigen.eigenvectors.unwrap();
+        let complex = a.data.mapv(|x| Complex64::new(x, 0.0));
+
+        let trace: Complex64 = eigen.eigenvalues.iter().sum();
+        assert!((trace - Complex64::new(a.trace(), 0.0)).norm() < 1e-10);
+        for (j, &lambda) in eigen.eigenvalues.iter().enumerate() {
+            let v = vectors.column(j);
+            let residual = complex.dot(&v) - v.mapv(|z| z * lambda);
+            assert!(residual.iter().all(|z| z.norm() < 1e-10));
+            assert!((v.iter().map(|z| z.norm_sqr()).sum::<f64>() - 1.0).abs() < 1e-12);
+        }
+
+        assert!(a.eigen(false).unwrap().eigenvectors.is_none());
+    }
+
+    #[test]
+    fn test_eigen_triangular_and_errors() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((3, 3), vec![1.0, 2.0, 3.0, 0.0, 4.0, 5.0, 0.0, 0.0, 6.0])
+                .unwrap(),
+        };
+        let mut values: Vec<f64> = a.eigenvalues().unwrap().iter().map(|z| z.re).collect();
+        values.sort_by(|x, y| x.partial_cmp(y).unwrap());
+
+        assert_vector_eq(
+            &Array1::from_vec(values),
+            &Array1::from_vec(vec![1.0, 4.0, 6.0]),
+            1e-12,
+        );
+        let non_square = Matrix {
+            data: Array2::zeros((2, 3)),
+        };
+        assert!(non_square.eigen(true).is_err());
+    }
+
+    #[test]
+    fn test_lstsq_overdetermined() {
+        // Fit y = c0 + c1 t through (0, 1), (1, 2), (2, 2), (3, 4)
+        let a = Matrix {
+            data: Array2::from_shape_vec((4, 2), vec![1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0])
+                .unwrap(),
+        };
+        let b = Vector {
+            data: Array1::from_vec(vec![1.0, 2.0, 2.0, 4.0]),
+        };
+        let result = a.lstsq(&b, None).unwrap();
+
+        assert_vector_eq(
+            &result.solution.data,
+            &Array1::from_vec(vec![0.9, 0.9]),
+            1e-12,
+        );
+        assert!((result.residuals.unwrap() - 0.7).abs() < 1e-12);
+        assert_eq!(result.rank, 2);
+        assert_eq!(result.singular_values.data.len(), 2);
+    }
+
+    #[test]
+    fn test_lstsq_minimum_norm() {
+        let underdetermined = Matrix {
+            data: Array2::from_shape_vec((1, 2), vec![1.0, 1.0]).unwrap(),
+        };
+        let b = Vector {
+            data: Array1::from_vec(vec![2.0]),
+        };
+        let result = underdetermined.lstsq(&b, None).unwrap();
+        assert_vector_eq(
+            &result.solution.data,
+            &Array1::from_vec(vec![1.0, 1.0]),
+            1e-12,
+        );
+        assert!(result.residuals.is_none());
+        assert_eq!(result.rank, 1);
+
+        let rank_deficient = Matrix {
+            data: Array2::from_shape_vec((3, 2), vec![1.0, 2.0, 2.0, 4.0, 3.0, 6.0]).unwrap(),
+        };
+        let b = Vector {
+            data: Array1::from_vec(vec![5.0, 10.0, 15.0]),
+        };
+        let result = rank_deficient.lstsq(&b, None).unwrap();
+        assert_vector_eq(
+            &result.solution.data,
+            &Array1::from_vec(vec![1.0, 2.0]),
+            1e-12,
+        );
+        assert!(result.residuals.is_none());
+        assert_eq!(result.rank, 1);
+
+        assert!(rank_deficient
+            .lstsq(
+                &Vector {
+                    data: Array1::zeros(2)
+                },
+                None
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_pinv_matches_inverse_and_normal_equations() {
+        let square = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![4.0, 7.0, 2.0, 6.0]).unwrap(),
+        };
+        let pinv = square.pinv(None).unwrap();
+        assert_matrix_eq(&pinv.data, &square.inverse().unwrap().data, 1e-12);
+
+        let tall = Matrix {
+            data: Array2::from_shape_vec((3, 2), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap(),
+        };
+        let pinv = tall.pinv(None).unwrap();
+        let normal = tall.transpose().multiply(&tall).unwrap().inverse().unwrap();
+        let expected = normal.multiply(&tall.transpose()).unwrap();
+        assert_eq!(pinv.data.dim(), (2, 3));
+        assert_matrix_eq(&pinv.data, &expected.data, 1e-12);
+    }
+
+    #[test]
+    fn test_pinv_rank_deficient() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 3), vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0]).unwrap(),
+        };
+        let pinv = a.pinv(None).unwrap();
+
+        // Penrose conditions A A+ A = A and A+ A A+ = A+
+        assert_matrix_eq(&a.data.dot(&pinv.data).dot(&a.data), &a.data, 1e-12);
+        assert_matrix_eq(&pinv.data.dot(&a.data).dot(&pinv.data), &pinv.data, 1e-12);
+        assert_matrix_eq(&(&pinv.data * 70.0), &a.data.t().to_owned(), 1e-12);
+    }
+
+    #[test]
+    fn test_pinv_cutoff() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![1.0, 0.0, 0.0, 1e-8]).unwrap(),
+        };
+
+        let pinv = a.pinv(None).unwrap();
+        assert!((pinv.data[[1, 1]] - 1e8).abs() < 1e-4);
+        let truncated = a.pinv(Some(1e-6)).unwrap();
+        assert_matrix_eq(
+            &truncated.data,
+            &Array2::from_shape_vec((2, 2), vec![1.0, 0.0, 0.0, 0.0]).unwrap(),
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn test_typed_errors() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap(),
+        };
+        assert_eq!(
+            a.multiply(&a).err(),
+            Some(LinAlgError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            a.inverse().err(),
+            Some(LinAlgError::NotSquare { rows: 2, cols: 3 })
+        );
+
+        let short = Vector {
+            data: Array1::from_vec(vec![1.0, 2.0]),
+        };
+        let long = Vector {
+            data: Array1::from_vec(vec![1.0, 2.0, 3.0]),
+        };
+        assert_eq!(
+            short.dot(&long).err(),
+            Some(LinAlgError::DimensionMismatch {
+                expected: 2,
+                found: 3
+            })
+        );
+
+        let rotation = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![0.0, -1.0, 1.0, 0.0]).unwrap(),
+        };
+        match rotation.eigenvector(10, 1e-10) {
+            Err(LinAlgError::NoConvergence { iterations, .. }) => assert_eq!(iterations, 10),
+            _ => panic!("power iteration on a rotation should not converge"),
+        }
+
+        let boxed: Box<dyn std::error::Error> = Box::new(LinAlgError::Singular { pivot: 0 });
+        assert_eq!(
+            boxed.to_string(),
+            "Matrix is singular: zero pivot in column 0"
+        );
+    }
+
+    #[test]
+    fn test_generic_f32_matrix() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![4.0f32, 7.0, 2.0, 6.0]).unwrap(),
+        };
+        let identity = Matrix::<f32>::identity(2);
+
+        assert_eq!(a.add(&identity).unwrap().data[[0, 0]], 5.0);
+        assert_eq!(a.multiply(&identity).unwrap().data, a.data);
+        assert_eq!(a.transpose().data[[0, 1]], 2.0);
+        assert!((a.determinant().unwrap() - 10.0).abs() < 1e-5);
+        let inverse = a.inverse().unwrap();
+        let expected = Array2::from_shape_vec((2, 2), vec![0.6f32, -0.7, -0.2, 0.4]).unwrap();
+        assert!(inverse
+            .data
+            .iter()
+            .zip(expected.iter())
+            .all(|(x, y)| (x - y).abs() < 1e-5));
+        assert_eq!(a.l1_norm(), 19.0);
+        assert_eq!(a.infinity_norm(), 11.0);
+        assert_eq!(a.kronecker_product(&identity).data.dim(), (4, 4));
+
+        let v = Vector {
+            data: Array1::from_vec(vec![3.0f32, 4.0]),
+        };
+        assert_eq!(v.l2_norm(), 5.0);
+    }
+
+    #[test]
+    fn test_generic_complex_matrix() {
+        let i = Complex64::new(0.0, 1.0);
+        let one = Complex64::new(1.0, 0.0);
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![one, i, i, 2.0 * one]).unwrap(),
+        };
+
+        // det = 1 * 2 - i * i = 3
+        assert!((a.determinant().unwrap() - 3.0 * one).norm() < 1e-12);
+        let product = a.multiply(&a.inverse().unwrap()).unwrap();
+        assert!(product
+            .data
+            .iter()
+            .zip(Matrix::<Complex64>::identity(2).data.iter())
+            .all(|(x, y)| (x - y).norm() < 1e-12));
+        assert!((a.l2_norm() - 7.0f64.sqrt()).abs() < 1e-12);
+        assert_eq!(a.trace(), 3.0 * one);
+
+        let pauli_x = Matrix {
+            data: Array2::from_shape_vec(
+                (2, 2),
+                vec![
+                    Complex32::new(0.0, 0.0),
+                    Complex32::new(1.0, 0.0),
+                    Complex32::new(1.0, 0.0),
+                    Complex32::new(0.0, 0.0),
+                ],
+            )
+            .unwrap(),
+        };
+        let two_qubit = pauli_x.kronecker_product(&pauli_x);
+        assert_eq!(two_qubit.data[[0, 3]], Complex32::new(1.0, 0.0));
+        assert_eq!(two_qubit.determinant().unwrap(), Complex32::new(1.0, 0.0));
+    }
+
+    #[test]
+    fn test_conjugate_transpose_and_vdot() {
+        let a = Matrix {
+            data: Array2::from_shape_vec(
+                (1, 2),
+                vec![Complex64::new(1.0, 2.0), Complex64::new(3.0, -4.0)],
+            )
+            .unwrap(),
+        };
+        let adjoint = a.conjugate_transpose();
+        assert_eq!(adjoint.data.dim(), (2, 1));
+        assert_eq!(adjoint.data[[0, 0]], Complex64::new(1.0, -2.0));
+        assert_eq!(adjoint.data[[1, 0]], Complex64::new(3.0, 4.0));
+
+        let u = Vector {
+            data: Array1::from_vec(vec![Complex64::new(0.0, 1.0), Complex64::new(1.0, 0.0)]),
+        };
+        let v = Vector {
+            data: Array1::from_vec(vec![Complex64::new(0.0, 1.0), Complex64::new(2.0, 0.0)]),
+        };
+        // dot does not conjugate, vdot conjugates the first argument
+        assert_eq!(u.dot(&v).unwrap(), Complex64::new(1.0, 0.0));
+        assert_eq!(u.vdot(&v).unwrap(), Complex64::new(3.0, 0.0));
+        assert!((u.vdot(&u).unwrap().re - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_complex_qr_is_unitary() {
+        let a = Matrix {
+            data: Array2::from_shape_vec(
+                (3, 2),
+                vec![
+                    Complex64::new(1.0, 1.0),
+                    Complex64::new(2.0, 0.0),
+                    Complex64::new(0.0, -1.0),
+                    Complex64::new(1.0, 3.0),
+                    Complex64::new(2.0, 2.0),
+                    Complex64::new(-1.0, 0.0),
+                ],
+            )
+            .unwrap(),
+        };
+        let (q, r) = a.qr_full();
+        let unitary = q.conjugate_transpose().multiply(&q).unwrap();
+        let identity = Matrix::<Complex64>::identity(3);
+        assert!(unitary
+            .data
+            .iter()
+            .zip(identity.data.iter())
+            .all(|(x, y)| (x - y).norm() < 1e-12));
+        let product = q.multiply(&r).unwrap();
+        assert!(product
+            .data
+            .iter()
+            .zip(a.data.iter())
+            .all(|(x, y)| (x - y).norm() < 1e-12));
+        assert!(r.data.diag().iter().all(|x| x.im == 0.0));
+
+        let square = Matrix {
+            data: a.data.slice(ndarray::s![..2, ..]).to_owned(),
+        };
+        let b = Vector {
+            data: Array1::from_vec(vec![Complex64::new(1.0, 0.0), Complex64::new(0.0, 1.0)]),
+        };
+        let qr = square.qr_factor();
+        let x = qr.solve(&b).unwrap();
+        let y = square.solve(&b).unwrap();
+        assert!(x
+            .data
+            .iter()
+            .zip(y.data.iter())
+            .all(|(p, q)| (p - q).norm() < 1e-12));
+        let determinant = qr.determinant().unwrap();
+        assert!((determinant - square.determinant().unwrap()).norm() < 1e-12);
+
+        let z = qr.solve_conjugate_transpose(&b).unwrap();
+        let back = square.conjugate_transpose().data.dot(&z.data);
+        assert!(back
+            .iter()
+            .zip(b.data.iter())
+            .all(|(p, q)| (p - q).norm() < 1e-12));
+    }
+
+    #[test]
+    fn test_hermitian_eigen() {
+        // Pauli Y has eigenvalues -1 and 1
+        let pauli_y = Matrix {
+            data: Array2::from_shape_vec(
+                (2, 2),
+                vec![
+                    Complex64::new(0.0, 0.0),
+                    Complex64::new(0.0, -1.0),
+                    Complex64::new(0.0, 1.0),
+                    Complex64::new(0.0, 0.0),
+                ],
+            )
+            .unwrap(),
+        };
+        let (values, vectors) = pauli_y.hermitian_eigen().unwrap();
+        assert_vector_eq(&values.data, &Array1::from_vec(vec![-1.0, 1.0]), 1e-12);
+
+        let diagonal = Array2::from_diag(&values.data.mapv(|x| Complex64::new(x, 0.0)));
+        let reconstructed = vectors
+            .multiply(&Matrix { data: diagonal })
+            .unwrap()
+            .multiply(&vectors.conjugate_transpose())
+            .unwrap();
+        assert!(reconstructed
+            .data
+            .iter()
+            .zip(pauli_y.data.iter())
+            .all(|(x, y)| (x - y).norm() < 1e-12));
+
+        let not_hermitian = Matrix {
+            data: Array2::from_shape_vec(
+                (2, 2),
+                vec![
+                    Complex64::new(1.0, 0.0),
+                    Complex64::new(0.0, 1.0),
+                    Complex64::new(0.0, 1.0),
+                    Complex64::new(1.0, 0.0),
+                ],
+            )
+            .unwrap(),
+        };
+        assert_eq!(
+            not_hermitian.hermitian_eigen().err(),
+            Some(LinAlgError::NotSymmetric)
+        );
+
+        let nan: Matrix = matrix![1.0, f64::NAN; f64::NAN, 1.0];
+        assert_eq!(nan.symmetric_eigen().err(), Some(LinAlgError::NonFinite));
+        let infinite = Matrix::from_diag(&vector![
+            Complex64::new(1.0, f64::INFINITY),
+            Complex64::new(2.0, 0.0)
+        ]);
+        assert_eq!(
+            infinite.hermitian_eigen().err(),
+            Some(LinAlgError::NonFinite)
+        );
+    }
+
+    #[test]
+    fn test_matrix_operators() {
+        let a = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 3.0, 4.0]).unwrap(),
+        };
+        let b = Matrix {
+            data: Array2::from_shape_vec((2, 2), vec![4.0, 3.0, 2.0, 1.0]).unwrap(),
+        };
+
+        assert_eq!((&a + &b).data, a.add(&b).unwrap().data);
+        assert_eq!((&a * &b).data, a.multiply(&b).unwrap().data);
+        assert_eq!(
+            (a.clone() - b.clone()).data,
+            Array2::from_shape_vec((2, 2), vec![-3.0, -1.0, 1.0, 3.0]).unwrap()
+        );
+        assert_eq!((-&a).data, a.data.mapv(|x| -x));
+        assert_eq!((&a * 2.0).data, (2.0_f64 * &a).data);
+        assert_eq!((2.0_f64 * a.clone()).data, &a.data * 2.0);
+
+        let v = Vector {
+            data: Array1::from_vec(vec![1.0, 1.0]),
+        };
+        assert_eq!((&a * &v).data, Array1::from_vec(vec![3.0, 7.0]));
+
+        let mut c = a.clone();
+        c += &b;
+        c -= b;
+        assert_eq!(c.data, a.data);
+
+        c[(0, 1)] = 5.0;
+        assert_eq!(c[(0, 1)], 5.0);
+        assert_eq!(c[(1, 0)], 3.0);
+    }
+
+    #[test]
+    fn test_vector_operators() {
+        let v1 = Vector {
+            data: Array1::from_vec(vec![1.0, 2.0, 3.0]),
+        };
+        let v2 = Vector {
+            data: Array1::from_vec(vec![4.0, 5.0, 6.0]),
+        };
+
+        assert_eq!((&v1 + &v2).data, Array1::from_vec(vec![5.0, 7.0, 9.0]));
+        assert_eq!((&v2 - &v1).data, Array1::from_vec(vec![3.0, 3.0, 3.0]));
+        assert_eq!((-v1.clone()).data, Array1::from_vec(vec![-1.0, -2.0, -3.0]));
+        assert_eq!((0.5_f64 * &v2).data, Array1::from_vec(vec![2.0, 2.5, 3.0]));
+
+        let mut v3 = v1.clone();
+        v3 += v2;
+        v3[0] = 0.0;
+        assert_eq!(v3[0], 0.0);
+        assert_eq!(v3[2], 9.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot add a 2x2 matrix and a 2x3 matrix")]
+    fn test_matrix_add_operator_shape_mismatch() {
+        let a: Matrix = Matrix::zero(2, 2);
+        let b: Matrix = Matrix::zero(2, 3);
+        let _ = a + b;
+    }
+
+    #[test]
+    #[should_panic(expected = "inner dimensions 2 and 3 differ")]
+    fn test_matrix_mul_operator_shape_mismatch() {
+        let a: Matrix = Matrix::zero(2, 2);
+        let b: Matrix = Matrix::zero(3, 2);
+        let _ = &a * &b;
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds for a 2x2 matrix")]
+    fn test_matrix_index_out_of_bounds() {
+        let a: Matrix = Matrix::identity(2);
+        let _ = a[(2, 0)];
+    }
+
+    #[test]
+    fn test_matrix_constructors() {
+        let from_rows = Matrix::from_rows(&[vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]).unwrap();
+        let from_cols = Matrix::from_cols(&[[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]]).unwrap();
+        let from_fn = Matrix::from_fn(2, 3, |i, j| (3 * i + j + 1) as f64);
+        let expected = Array2::from_shape_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+        assert_eq!(from_rows.data, expected);
+        assert_eq!(from_cols.data, expected);
+        assert_eq!(from_fn.data, expected);
+        assert_eq!(matrix![1.0, 2.0, 3.0; 4.0, 5.0, 6.0].data, expected);
+
+        let diagonal = Matrix::from_diag(&vector![1.0, 2.0]);
+        assert_eq!(diagonal.data, matrix![1.0, 0.0; 0.0, 2.0].data);
+        assert_eq!(
+            Matrix::<f64>::ones(2, 2).data,
+            Matrix::filled(2, 2, 1.0).data
+        );
+
+        let empty: Matrix = Matrix::from_rows::<Vec<f64>>(&[]).unwrap();
+        assert_eq!(empty.data.dim(), (0, 0));
+
+        assert_eq!(
+            Matrix::from_rows(&[vec![1.0, 2.0], vec![3.0]]).err(),
+            Some(LinAlgError::DimensionMismatch {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Matrix::from_cols(&[vec![1.0], vec![2.0, 3.0]]).err(),
+            Some(LinAlgError::DimensionMismatch {
+                expected: 1,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_vector_constructors() {
+        let v = Vector::from_slice(&[1.0, 2.0, 3.0]);
+        assert_eq!(v.data, Array1::from_vec(vec![1.0, 2.0, 3.0]));
+        assert_eq!(vector![1.0, 2.0, 3.0].data, v.data);
+
+        let complex = vector![Complex64::new(1.0, 1.0), Complex64::new(0.0, 2.0)];
+        assert_eq!(complex.data.len(), 2);
+    }
+
+    #[test]
+    fn test_matrix_display() {
+        let a = matrix![1.0, -2.5; 10.0, 4.0];
+        assert_eq!(format!("{}", a), "[[   1, -2.5],\n [  10,    4]]");
+        assert_eq!(format!("{:.2}", a), "[[ 1.00, -2.50],\n [10.00,  4.00]]");
+        assert_eq!(
+            format!("{:.1e}", a),
+            "[[ 1.0e0, -2.5e0],\n [ 1.0e1,  4.0e0]]"
+        );
+
+        let large = Matrix::from_fn(40, 40, |i, j| (i * 40 + j) as f64);
+        let rendered = format!("{}", large);
+        assert_eq!(rendered.lines().count(), 7);
+        assert!(rendered.starts_with("[[   0,    1,    2,  ...,   37,   38,   39],"));
+        assert!(rendered.contains("\n ...,\n"));
+        assert!(rendered.ends_with("[1560, 1561, 1562,  ..., 1597, 1598, 1599]]"));
+
+        let empty: Matrix = Matrix::zero(0, 3);
+        assert_eq!(format!("{}", empty), "[]");
+    }
+
+    #[test]
+    fn test_vector_display() {
+        let v = vector![1.0, 22.0, 333.0];
+        assert_eq!(format!("{}", v), "[  1,  22, 333]");
+        assert_eq!(format!("{:.1}", v), "[  1.0,  22.0, 333.0]");
+        assert_eq!(format!("{:e}", v), "[   1e0,  2.2e1, 3.33e2]");
+        assert_eq!(format!("{:4}", vector![1.0, 2.0]), "[   1,    2]");
+    }
+
+    #[test]
+    fn test_latex_and_markdown_export() {
+        let a = matrix![1.0, 2.0; 3.0, 4.5];
+        assert_eq!(
+            a.to_latex(),
+            "\\begin{bmatrix}\n  1 & 2 \\\\\n  3 & 4.5\n\\end{bmatrix}"
+        );
+        assert_eq!(
+            a.to_markdown(),
+            "| 0 | 1 |\n|---:|---:|\n| 1 | 2 |\n| 3 | 4.5 |"
+        );
+
+        let v = vector![1.0, 2.0];
+        assert_eq!(
+            v.to_latex(),
+            "\\begin{bmatrix}\n  1 \\\\\n  2\n\\end{bmatrix}"
+        );
+        assert_eq!(v.to_markdown(), "| 0 |\n|---:|\n| 1 |\n| 2 |");
+    }
+
+    #[test]
+    fn test_matrix_multiply_blocked_matches_dot() {
+        // Shapes straddle the row bands used for parallel products.
+        for &(m, k, n) in &[(1, 1, 1), (3, 5, 2), (70, 300, 13), (129, 257, 9)] {
+            let a: Matrix = Matrix::from_fn(m, k, |i, j| ((i * 7 + j * 3) % 11) as f64 - 5.0);
+            let b: Matrix = Matrix::from_fn(k, n, |i, j| ((i * 5 + j * 2) % 13) as f64 - 6.0);
+            assert_eq!(a.multiply(&b).unwrap().data, a.data.dot(&b.data));
+        }
+
+        // Non-contiguous (transposed) operands.
+        let a: Matrix = Matrix::from_fn(9, 17, |i, j| (i + 2 * j) as f64);
+        let a_t = Matrix {
+            data: a.data.t().to_owned(),
+        };
+        assert_eq!(a_t.multiply(&a).unwrap().data, a.data.t().dot(&a.data));
+
+        let empty_inner: Matrix = Matrix::zero(3, 0);
+        let product = empty_inner.multiply(&Matrix::zero(0, 4)).unwrap();
+        assert_eq!(product.data, Array2::<f64>::zeros((3, 4)));
+
+        let c = Matrix::from_fn(5, 6, |i, j| Complex64::new(i as f64, j as f64));
+        let d = Matrix::from_fn(6, 3, |i, j| Complex64::new(j as f64, -(i as f64)));
+        assert_eq!(c.multiply(&d).unwrap().data, c.data.dot(&d.data));
+    }
+
+    #[test]
+    fn test_matrix_multiply_strassen() {
+        // A small cutoff forces several levels of recursion, including odd
+        // and rectangular shapes that need padding.
+        for &(m, k, n) in &[(64, 64, 64), (37, 50, 23), (100, 7, 90), (5, 5, 5)] {
+            let a: Matrix =
+                Matrix::from_fn(m, k, |i, j| ((i * 31 + j * 17) % 23) as f64 / 7.0 - 1.5);
+            let b: Matrix =
+                Matrix::from_fn(k, n, |i, j| ((i * 13 + j * 29) % 19) as f64 / 5.0 - 2.0);
+            let classic = a.multiply(&b).unwrap();
+            let strassen = a.multiply_strassen(&b, 4).unwrap();
+            assert_eq!(strassen.data.dim(), (m, n));
+            assert_matrix_eq(&strassen.data, &classic.data, 1e-10);
+        }
+
+        let c = Matrix::from_fn(33, 33, |i, j| Complex64::new(i as f64, j as f64 - 16.0));
+        let strassen = c.multiply_strassen(&c, 8).unwrap();
+        let classic = c.multiply(&c).unwrap();
+        assert!(strassen
+            .data
+            .iter()
+            .zip(classic.data.iter())
+            .all(|(x, y)| (x - y).norm() < 1e-9));
+
+        let a: Matrix = Matrix::zero(2, 3);
+        assert_eq!(
+            a.multiply_strassen(&a, 64).err(),
+            Some(LinAlgError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    // Timing comparison against ndarray's `dot`, which runs the same
+    // matrixmultiply kernels on one thread, so `multiply` must keep up with it.
+    // Wall-clock timing is only meaningful in an unloaded release build:
+    // cargo test --release -- --ignored
+    #[test]
+    #[ignore]
+    fn test_matrix_multiply_benchmark() {
+        let n = 1000;
+        let a: Matrix = Matrix::from_fn(n, n, |i, j| ((i * 7 + j * 3) % 11) as f64 - 5.0);
+        let b: Matrix = Matrix::from_fn(n, n, |i, j| ((i * 5 + j * 2) % 13) as f64 - 6.0);
+
+        let mut multiply = Duration::MAX;
+        let mut dot = Duration::MAX;
+        for _ in 0..5 {
+            let start = Instant::now();
+            let product = a.multiply(&b).unwrap();
+            multiply = multiply.min(start.elapsed());
+
+            let start = Instant::now();
+            let reference = a.data.dot(&b.data);
+            dot = dot.min(start.elapsed());
+
+            assert_eq!(product.data, reference);
+        }
+
+        assert!(
+            multiply.as_secs_f64() <= 1.5 * dot.as_secs_f64(),
+            "multiply took {:?}, ndarray dot took {:?}",
+            multiply,
+            dot
+        );
+    }
+
+    #[test]
+    fn test_parallel_config() {
+        let a: Matrix = Matrix::from_fn(
+            40,
+            40,
+            |i, j| if i == j { 50.0 } else { ((i + j) % 7) as f64 },
+        );
+        let v = Vector::from_slice(&(0..5000).map(|i| (i % 9) as f64).collect::<Vec<_>>());
+        let expected_product = a.multiply(&a).unwrap().data;
+        let expected_inverse = a.inverse().unwrap().data;
+        let expected_dot = v.dot(&v).unwrap();
+
+        // The configuration is global, so every setting is exercised from a
+        // single test and the default is restored at the end.
+        let configs = vec![
+            ParallelConfig::serial(),
+            ParallelConfig::new(0),
+            ParallelConfig::new(0).with_threads(2).unwrap(),
+        ];
+        for config in configs {
+            set_parallel_config(config.clone());
+            assert_eq!(parallel_config().threshold, config.threshold);
+            assert_eq!(a.multiply(&a).unwrap().data, expected_product);
+            assert_matrix_eq(&a.inverse().unwrap().data, &expected_inverse, 1e-12);
+            assert_eq!(v.dot(&v).unwrap(), expected_dot);
+            assert_eq!(v.add(&v).unwrap().data, &v.data * 2.0);
+            assert_eq!(v.l1_norm(), v.data.sum());
+        }
+
+        set_parallel_config(ParallelConfig::default());
+        assert_eq!(parallel_config().threshold, DEFAULT_PARALLEL_THRESHOLD);
+        assert!(parallel_config().pool.is_none());
+    }
+
+    #[test]
+    fn test_vector_elementwise_operations() {
+        let a = Vector::from_slice(&[1.0, 2.0, 4.0]);
+        let b = Vector::from_slice(&[4.0, 6.0, 8.0]);
+
+        assert_eq!(
+            b.sub(&a).unwrap().data,
+            Array1::from_vec(vec![3.0, 4.0, 4.0])
+        );
+        assert_eq!(
+            a.hadamard(&b).unwrap().data,
+            Array1::from_vec(vec![4.0, 12.0, 32.0])
+        );
+        assert_eq!(
+            b.div(&a).unwrap().data,
+            Array1::from_vec(vec![4.0, 3.0, 2.0])
+        );
+        assert_eq!(a.scale(-2.0).data, Array1::from_vec(vec![-2.0, -4.0, -8.0]));
+
+        let mut y = b.clone();
+        y.axpy(0.5, &a).unwrap();
+        assert_eq!(y.data, Array1::from_vec(vec![4.5, 7.0, 10.0]));
+
+        let short = Vector::from_slice(&[1.0, 2.0]);
+        let mismatch = Some(LinAlgError::DimensionMismatch {
+            expected: 3,
+            found: 2,
+        });
+        assert_eq!(a.add(&short).err(), mismatch);
+        assert_eq!(a.sub(&short).err(), mismatch);
+        assert_eq!(a.hadamard(&short).err(), mismatch);
+        assert_eq!(a.div(&short).err(), mismatch);
+        assert_eq!(y.axpy(1.0, &short).err(), mismatch);
+        assert_eq!(a.dot(&short).err(), mismatch);
+    }
+
+    #[test]
+    fn test_vector_operations_non_contiguous() {
+        // Every other element, and a reversed vector, are not contiguous in
+        // standard order.
+        let mut strided = Array1::from_vec(vec![1.0, -1.0, 2.0, -1.0, 3.0]);
+        strided.slice_collapse(s![..;2]);
+        let mut reversed = Array1::from_vec(vec![6.0, 5.0, 4.0]);
+        reversed.invert_axis(Axis(0));
+        assert!(strided.as_slice().is_none());
+        let a = Vector { data: strided };
+        let b = Vector { data: reversed };
+
+        assert_eq!(
+            a.add(&b).unwrap().data,
+            Array1::from_vec(vec![5.0, 7.0, 9.0])
+        );
+        assert_eq!(a.dot(&b).unwrap(), 32.0);
+        assert_eq!(
+            b.sub(&a).unwrap().data,
+            Array1::from_vec(vec![3.0, 3.0, 3.0])
+        );
+        assert_eq!(a.l1_norm(), 6.0);
+
+        // First column of a row-major matrix, taken without copying.
+        let matrix = Array2::from_shape_vec((3, 2), vec![1.0, 9.0, 2.0, 9.0, 3.0, 9.0]).unwrap();
+        let c = Vector {
+            data: matrix.reversed_axes().index_axis_move(Axis(0), 0),
+        };
+        assert_eq!(
+            c.add(&a).unwrap().data,
+            Array1::from_vec(vec![2.0, 4.0, 6.0])
+        );
+    }
+
+    #[test]
+    fn test_vector_cross_and_outer() {
+        let x = vector![1.0, 0.0, 0.0];
+        let y = vector![0.0, 1.0, 0.0];
+        assert_eq!(
+            x.cross(&y).unwrap().data,
+            Array1::from_vec(vec![0.0, 0.0, 1.0])
+        );
+        let a = vector![1.0, 2.0, 3.0];
+        let b = vector![4.0, 5.0, 6.0];
+        let c = a.cross(&b).unwrap();
+        assert_eq!(c.data, Array1::from_vec(vec![-3.0, 6.0, -3.0]));
+        assert_eq!(c.dot(&a).unwrap(), 0.0);
+        assert_eq!(
+            vector![1.0, 2.0].cross(&a).err(),
+            Some(LinAlgError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+
+        let outer = vector![1.0, 2.0].outer(&a);
+        assert_eq!(outer.data, matrix![1.0, 2.0, 3.0; 2.0, 4.0, 6.0].data);
+    }
+
+    #[test]
+    fn test_vector_angle_and_projection() {
+        let x: Vector = vector![2.0, 0.0];
+        let diagonal = vector![1.0, 1.0];
+        assert!((x.angle_between(&diagonal).unwrap() - std::f64::consts::FRAC_PI_4).abs() < 1e-12);
+        assert!((x.angle_between(&x.scale(-1.0)).unwrap() - std::f64::consts::PI).abs() < 1e-12);
+        assert_eq!(x.angle_between(&x).unwrap(), 0.0);
+
+        let v = vector![3.0, 4.0];
+        let projection = v.project_onto(&x).unwrap();
+        let rejection = v.reject_from(&x).unwrap();
+        assert_vector_eq(&projection.data, &Array1::from_vec(vec![3.0, 0.0]), 1e-12);
+        assert_vector_eq(&rejection.data, &Array1::from_vec(vec![0.0, 4.0]), 1e-12);
+        assert_vector_eq(&projection.add(&rejection).unwrap().data, &v.data, 1e-12);
+
+        let zero = vector![0.0, 0.0];
+        assert_eq!(v.project_onto(&zero).err(), Some(LinAlgError::ZeroVector));
+        assert_eq!(v.angle_between(&zero).err(), Some(LinAlgError::ZeroVector));
+
+        let u = vector![Complex64::new(0.0, 1.0), Complex64::new(1.0, 0.0)];
+        let w = vector![Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)];
+        let rejection = u.reject_from(&w).unwrap();
+        assert!(rejection.vdot(&w).unwrap().norm() < 1e-12);
+    }
+
+    #[test]
+    fn test_vector_distance() {
+        let a: Vector = vector![1.0, 2.0, 3.0];
+        let b = vector![4.0, 6.0, 3.0];
+        assert_eq!(a.distance(&b, DistanceKind::Euclidean).unwrap(), 5.0);
+        assert_eq!(a.distance(&b, DistanceKind::Manhattan).unwrap(), 7.0);
+        assert_eq!(a.distance(&b, DistanceKind::Chebyshev).unwrap(), 4.0);
+        assert!(a
+            .distance(&vector![f64::NAN, 0.0, 0.0], DistanceKind::Chebyshev)
+            .unwrap()
+            .is_nan());
+        assert!(
+            a.distance(&a.scale(3.0), DistanceKind::Cosine)
+                .unwrap()
+                .abs()
+                < 1e-12
+        );
+        let orthogonal: f64 = vector![1.0, 0.0]
+            .distance(&vector![0.0, 1.0], DistanceKind::Cosine)
+            .unwrap();
+        assert!((orthogonal - 1.0).abs() < 1e-12);
+
+        assert_eq!(
+            a.distance(&vector![1.0], DistanceKind::Euclidean).err(),
+            Some(LinAlgError::DimensionMismatch {
+                expected: 3,
+                found: 1
+            })
+        );
+        assert_eq!(
+            a.distance(&vector![0.0, 0.0, 0.0], DistanceKind::Cosine)
+                .err(),
+            Some(LinAlgError::ZeroVector)
+        );
+    }
+
+    #[test]
+    fn test_matrix_norm_kinds() {
+        let a: Matrix = matrix![1.0, -2.0; -3.0, 4.0];
+        assert!((a.norm(NormKind::Frobenius).unwrap() - 30.0f64.sqrt()).abs() < 1e-12);
+        assert_eq!(a.norm(NormKind::One).unwrap(), 6.0);
+        assert_eq!(a.norm(NormKind::Infinity).unwrap(), 7.0);
+        assert_eq!(a.norm(NormKind::MaxAbs).unwrap(), 4.0);
+
+        // Singular values of [[1, -2], [-3, 4]] are sqrt(15 +- sqrt(221)).
+        let root = 221.0f64.sqrt();
+        let (s1, s2) = ((15.0 + root).sqrt(), (15.0 - root).sqrt());
+        assert!((a.norm(NormKind::Spectral).unwrap() - s1).abs() < 1e-12);
+        assert!((a.norm(NormKind::Nuclear).unwrap() - (s1 + s2)).abs() < 1e-12);
+
+        // The existing methods are the entrywise 1-norm, the Frobenius norm and
+        // the induced infinity-norm.
+        assert_eq!(a.l1_norm(), 10.0);
+        assert_eq!(a.l2_norm(), a.norm(NormKind::Frobenius).unwrap());
+        assert_eq!(a.infinity_norm(), a.norm(NormKind::Infinity).unwrap());
+
+        let rectangular: Matrix = matrix![3.0, 0.0, 0.0; 0.0, 0.0, -5.0];
+        assert_eq!(rectangular.norm(NormKind::Spectral).unwrap(), 5.0);
+        assert_eq!(rectangular.norm(NormKind::Nuclear).unwrap(), 8.0);
+
+        let empty: Matrix = Matrix::zero(0, 0);
+        assert_eq!(empty.norm(NormKind::Spectral).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_matrix_norm_nan_and_infinity() {
+        let a: Matrix = matrix![1.0, f64::NAN; -3.0, 4.0];
+        assert!(a.infinity_norm().is_nan());
+        for &kind in &[
+            NormKind::Frobenius,
+            NormKind::One,
+            NormKind::Infinity,
+            NormKind::Spectral,
+            NormKind::Nuclear,
+            NormKind::MaxAbs,
+        ] {
+            assert!(a.norm(kind).unwrap().is_nan(), "{:?}", kind);
+        }
+
+        // NaN anywhere wins over a larger finite or infinite entry.
+        let mixed: Matrix = matrix![f64::INFINITY, 0.0; 0.0, f64::NAN];
+        assert!(mixed.norm(NormKind::MaxAbs).unwrap().is_nan());
+        assert!(mixed.norm(NormKind::Spectral).unwrap().is_nan());
+
+        let b: Matrix = matrix![1.0, f64::NEG_INFINITY; 0.0, 2.0];
+        assert_eq!(b.norm(NormKind::One).unwrap(), f64::INFINITY);
+        assert_eq!(b.norm(NormKind::Spectral).unwrap(), f64::INFINITY);
+        assert_eq!(b.norm(NormKind::MaxAbs).unwrap(), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_vector_norm_p_nan() {
+        let v: Vector = vector![f64::NAN, 5.0, 0.0];
+        assert!(v.norm_p(f64::INFINITY).is_nan());
+        assert!(v.norm_p(f64::NEG_INFINITY).is_nan());
+        assert!(v.norm_p(2.0).is_nan());
+        assert!(v.norm_p(-1.0).is_nan());
+
+        let infinite: Vector = vector![f64::INFINITY, 1.0];
+        assert_eq!(infinite.norm_p(2.0), f64::INFINITY);
+        assert_eq!(infinite.norm_p(3.0), f64::INFINITY);
+        assert_eq!(infinite.norm_p(0.5), f64::INFINITY);
+
+        let complex = vector![Complex64::new(1.0, f64::NAN), Complex64::new(9.0, 0.0)];
+        assert!(complex.norm_p(f64::INFINITY).is_nan());
+    }
+
+    #[test]
+    fn test_vector_norm_p() {
+        let v: Vector = vector![3.0, -4.0, 0.0];
+        assert_eq!(v.norm_p(1.0), 7.0);
+        assert!((v.norm_p(2.0) - 5.0).abs() < 1e-12);
+        assert!((v.norm_p(3.0) - 91.0f64.cbrt()).abs() < 1e-12);
+        assert_eq!(v.norm_p(f64::INFINITY), 4.0);
+        assert_eq!(v.norm_p(f64::NEG_INFINITY), 0.0);
+        assert_eq!(v.norm_p(0.0), 2.0);
+        assert_eq!(v.norm_p(-1.0), 0.0);
+
+        let w: Vector = vector![2.0, 2.0];
+        assert!((w.norm_p(-1.0) - 1.0).abs() < 1e-12);
+        assert!((w.norm_p(0.5) - 8.0).abs() < 1e-12);
+
+        // No overflow for entries whose squares exceed f64::MAX.
+        let huge: Vector = vector![3e200, 4e200];
+        assert!((huge.norm_p(2.0) / 5e200 - 1.0).abs() < 1e-12);
+
+        let complex = vector![Complex64::new(3.0, 4.0), Complex64::new(0.0, 0.0)];
+        assert_eq!(complex.norm_p(f64::INFINITY), 5.0);
+        assert!((complex.norm_p(2.0) - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_matrix_condition_number() {
+        let a: Matrix = matrix![4.0, 1.0; 2.0, 3.0];
+        let inverse = a.inverse().unwrap();
+        let s = a.singular_values().unwrap().data;
+        assert!((a.cond(NormKind::Spectral).unwrap() - s[0] / s[1]).abs() < 1e-12);
+        for &kind in &[NormKind::One, NormKind::Infinity, NormKind::Frobenius] {
+            let expected = a.norm(kind).unwrap() * inverse.norm(kind).unwrap();
+            assert!((a.cond(kind).unwrap() - expected).abs() < 1e-12);
+        }
+
+        // For this small matrix the estimate hits the exact value.
+        assert!((a.rcond_estimate().unwrap() * a.cond(NormKind::One).unwrap() - 1.0).abs() < 1e-12);
+
+        let identity: Matrix = Matrix::identity(3);
+        assert!((identity.cond(NormKind::Spectral).unwrap() - 1.0).abs() < 1e-12);
+        assert_eq!(identity.rcond_estimate().unwrap(), 1.0);
+
+        let singular: Matrix = matrix![1.0, 2.0; 2.0, 4.0];
+        assert_eq!(singular.cond(NormKind::One).unwrap(), f64::INFINITY);
+        assert_eq!(singular.cond(NormKind::Spectral).unwrap(), f64::INFINITY);
+        assert_eq!(singular.rcond_estimate().unwrap(), 0.0);
+
+        let tall: Matrix = matrix![1.0, 0.0; 0.0, 2.0; 0.0, 0.0];
+        assert!((tall.cond(NormKind::Spectral).unwrap() - 2.0).abs() < 1e-12);
+        assert_eq!(
+            tall.cond(NormKind::One).err(),
+            Some(LinAlgError::NotSquare { rows: 3, cols: 2 })
+        );
+        assert_eq!(
+            tall.rcond_estimate().err(),
+            Some(LinAlgError::NotSquare { rows: 3, cols: 2 })
+        );
+    }
+
+    #[test]
+    fn test_checked_solve_refuses_ill_conditioned() {
+        let a: Matrix = matrix![4.0, 1.0; 2.0, 3.0];
+        let b = vector![1.0, 2.0];
+        assert_vector_eq(
+            &a.solve_checked(&b, 1e-8).unwrap().data,
+            &a.solve(&b).unwrap().data,
+            1e-15,
+        );
+        assert_matrix_eq(
+            &a.inverse_checked(1e-8).unwrap().data,
+            &a.inverse().unwrap().data,
+            1e-15,
+        );
+
+        let hilbert: Matrix = Matrix::from_fn(12, 12, |i, j| 1.0 / (i + j + 1) as f64);
+        let rcond = hilbert.rcond_estimate().unwrap();
+        assert!(rcond < 1e-14);
+        assert!(hilbert.inverse().is_ok());
+        assert_eq!(
+            hilbert.inverse_checked(1e-10).err(),
+            Some(LinAlgError::IllConditioned { rcond })
+        );
+
+        let singular: Matrix = matrix![1.0, 2.0; 2.0, 4.0];
+        assert_eq!(
+            singular.solve_checked(&b, 1e-10).err(),
+            Some(LinAlgError::IllConditioned { rcond: 0.0 })
+        );
+
+        let nan: Matrix = matrix![4.0, f64::NAN; 2.0, 3.0];
+        assert_eq!(
+            nan.solve_checked(&b, 1e-10).err(),
+            Some(LinAlgError::NonFinite)
+        );
+        assert_eq!(
+            nan.inverse_checked(1e-10).err(),
+            Some(LinAlgError::NonFinite)
+        );
+        assert_eq!(nan.rcond_estimate().err(), Some(LinAlgError::NonFinite));
+    }
+
+    #[test]
+    fn test_matrix_rank() {
+        let full: Matrix = matrix![1.0, 2.0; 3.0, 4.0];
+        assert_eq!(full.rank(None).unwrap(), 2);
+        let deficient: Matrix = matrix![1.0, 2.0, 3.0; 2.0, 4.0, 6.0; 1.0, 0.0, 1.0];
+        assert_eq!(deficient.rank(None).unwrap(), 2);
+        let nearly: Matrix = matrix![1.0, 0.0; 0.0, 1e-9];
+        assert_eq!(nearly.rank(None).unwrap(), 2);
+        assert_eq!(nearly.rank(Some(1e-6)).unwrap(), 1);
+        let zero: Matrix = Matrix::zero(3, 2);
+        assert_eq!(zero.rank(None).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_matrix_fundamental_subspaces() {
+        let a: Matrix = matrix![1.0, 2.0, 3.0; 2.0, 4.0, 6.0; 1.0, 0.0, 1.0; 0.0, 2.0, 2.0];
+        let (m, n) = a.data.dim();
+        let column_space = a.column_space(None).unwrap();
+        let left_null_space = a.left_null_space(None).unwrap();
+        let row_space = a.row_space(None).unwrap();
+        let null_space = a.null_space(None).unwrap();
+        assert_eq!(column_space.data.dim(), (m, 2));
+        assert_eq!(left_null_space.data.dim(), (m, 2));
+        assert_eq!(row_space.data.dim(), (n, 2));
+        assert_eq!(null_space.data.dim(), (n, 1));
+
+        for basis in &[&column_space, &left_null_space, &row_space, &null_space] {
+            let gram = basis.data.t().dot(&basis.data);
+            assert_matrix_eq(&gram, &Array2::eye(basis.data.ncols()), 1e-12);
+        }
+
+        // A N = 0, A^T L = 0, and each space is orthogonal to its complement.
+        assert_matrix_eq(&a.data.dot(&null_space.data), &Array2::zeros((m, 1)), 1e-12);
+        assert_matrix_eq(
+            &a.data.t().dot(&left_null_space.data),
+            &Array2::zeros((n, 2)),
+            1e-12,
+        );
+        assert_matrix_eq(
+            &row_space.data.t().dot(&null_space.data),
+            &Array2::zeros((2, 1)),
+            1e-12,
+        );
+        assert_matrix_eq(
+            &column_space.data.t().dot(&left_null_space.data),
+            &Array2::zeros((2, 2)),
+            1e-12,
+        );
+
+        let expected = Array1::from_vec(vec![1.0, 1.0, -1.0]) / 3.0f64.sqrt();
+        let direction = null_space.data.column(0).to_owned();
+        assert!(
+            (&direction - &expected).iter().all(|x| x.abs() < 1e-12)
+                || (&direction + &expected).iter().all(|x| x.abs() < 1e-12)
+        );
+
+        let invertible: Matrix = matrix![2.0, 1.0; 1.0, 3.0];
+        assert_eq!(invertible.null_space(None).unwrap().data.dim(), (2, 0));
+        assert_eq!(invertible.left_null_space(None).unwrap().data.dim(), (2, 0));
+    }
+
+    #[test]
+    fn test_matrix_rref() {
+        let a: Matrix = matrix![1.0, 2.0, -1.0, -4.0; 2.0, 3.0, -1.0, -11.0; -2.0, 0.0, -3.0, 22.0];
+        let (r, pivots) = a.rref();
+        assert_eq!(pivots, vec![0, 1, 2]);
+        assert_matrix_eq(
+            &r.data,
+            &matrix![1.0, 0.0, 0.0, -8.0; 0.0, 1.0, 0.0, 1.0; 0.0, 0.0, 1.0, -2.0].data,
+            1e-12,
+        );
+
+        let deficient: Matrix = matrix![1.0, 2.0, 3.0; 2.0, 4.0, 6.0; 1.0, 0.0, 1.0];
+        let (r, pivots) = deficient.rref();
+        assert_eq!(pivots, vec![0, 1]);
+        assert_matrix_eq(
+            &r.data,
+            &matrix![1.0, 0.0, 1.0; 0.0, 1.0, 1.0; 0.0, 0.0, 0.0].data,
+            1e-12,
+        );
+        assert_eq!(r.data.row(2).to_vec(), vec![0.0, 0.0, 0.0]);
+        assert_eq!(pivots.len(), deficient.rank(None).unwrap());
+
+        let wide: Matrix = matrix![0.0, 0.0, 2.0; 0.0, 0.0, 4.0];
+        let (r, pivots) = wide.rref();
+        assert_eq!(pivots, vec![2]);
+        assert_eq!(r.data, matrix![0.0, 0.0, 1.0; 0.0, 0.0, 0.0].data);
+
+        let complex = matrix![Complex64::new(0.0, 1.0), Complex64::new(1.0, 0.0); Complex64::new(1.0, 0.0), Complex64::new(0.0, -1.0)];
+        let (_, pivots) = complex.rref();
+        assert_eq!(pivots, vec![0]);
+    }
+
     // Helper function for comparing vectors
     fn assert_vector_eq(a: &Array1<f64>, b: &Array1<f64>, tol: f64) {
         assert!(a.iter().zip(b.iter()).all(|(&x, &y)| (x - y).abs() < tol));